@@ -7,6 +7,14 @@ template RollupBatch(nTx) {
     // Public Inputs
     signal input oldRoot;
     signal input newRoot;
+    // Forced-queue accumulators before and after the forced withdrawals
+    // this batch processed (see contracts/forced.rs). The contract computes
+    // both from its queue, so a proof only verifies for the range the queue
+    // requires. This prototype does not hash in-circuit, so it binds them
+    // as public inputs without checking the batch's withdrawals against the
+    // queue entries.
+    signal input forcedAccFrom;
+    signal input forcedAccTo;
 
     // Private Inputs
     signal input tx_amounts[nTx];
@@ -42,4 +50,4 @@ template RollupBatch(nTx) {
     intermediary_roots[nTx] === newRoot;
 }
 
-component main {public [oldRoot, newRoot]} = RollupBatch(10);
+component main {public [oldRoot, newRoot, forcedAccFrom, forcedAccTo]} = RollupBatch(10);
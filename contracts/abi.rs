@@ -0,0 +1,439 @@
+//! Entry-point descriptors
+//!
+//! Each entry point is declared once as an `EntryPointDef`; the same table
+//! builds the `EntryPoints` passed to `storage::new_contract` and renders the
+//! JSON ABI that clients use to build deploys. The ABI also lists the error
+//! codes the contract reverts with, so clients can explain failed deploys.
+//!
+//! Every contract variant builds its entry points through this module, and
+//! each is a Cargo target so the tables are compiled with it.
+
+use alloc::{boxed::Box, format, string::String};
+use casper_accelerate_shared::error::Error;
+use casper_types::{
+    addressable_entity::{
+        EntityEntryPoint, EntryPointAccess, EntryPointPayment, EntryPointType, EntryPoints,
+    },
+    CLType, Parameter,
+};
+
+/// Argument and return types used by the bridge entry points
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ArgType {
+    Unit,
+    Bool,
+    U8,
+    U32,
+    U64,
+    U256,
+    U512,
+    String,
+    URef,
+    Key,
+    AccountHash,
+    PublicKey,
+    /// `List<U8>`, used for proofs and other variable-length payloads
+    Bytes,
+    /// `ByteArray(32)`, used for roots, hashes and nullifiers
+    Hash,
+    /// `Tuple3<U64, U64, U64>`, used for small fixed sets of counters
+    U64Triple,
+}
+
+impl ArgType {
+    pub fn cl_type(self) -> CLType {
+        match self {
+            ArgType::Unit => CLType::Unit,
+            ArgType::Bool => CLType::Bool,
+            ArgType::U8 => CLType::U8,
+            ArgType::U32 => CLType::U32,
+            ArgType::U64 => CLType::U64,
+            ArgType::U256 => CLType::U256,
+            ArgType::U512 => CLType::U512,
+            ArgType::String => CLType::String,
+            ArgType::URef => CLType::URef,
+            ArgType::Key => CLType::Key,
+            ArgType::AccountHash => CLType::ByteArray(32),
+            ArgType::PublicKey => CLType::PublicKey,
+            ArgType::Bytes => CLType::List(Box::new(CLType::U8)),
+            ArgType::Hash => CLType::ByteArray(32),
+            ArgType::U64Triple => CLType::Tuple3([
+                Box::new(CLType::U64),
+                Box::new(CLType::U64),
+                Box::new(CLType::U64),
+            ]),
+        }
+    }
+
+    /// Name used in the JSON ABI, matching casper-client's CLType spelling
+    ///
+    /// Account hashes are named apart from other 32-byte arrays so clients
+    /// know to pass `account-hash-…`, although both are `ByteArray(32)`.
+    pub fn abi_name(self) -> &'static str {
+        match self {
+            ArgType::Unit => "Unit",
+            ArgType::Bool => "Bool",
+            ArgType::U8 => "U8",
+            ArgType::U32 => "U32",
+            ArgType::U64 => "U64",
+            ArgType::U256 => "U256",
+            ArgType::U512 => "U512",
+            ArgType::String => "String",
+            ArgType::URef => "URef",
+            ArgType::Key => "Key",
+            ArgType::AccountHash => "AccountHash",
+            ArgType::PublicKey => "PublicKey",
+            ArgType::Bytes => "List<U8>",
+            ArgType::Hash => "ByteArray32",
+            ArgType::U64Triple => "Tuple3<U64,U64,U64>",
+        }
+    }
+
+    /// TypeScript type a client passes or receives for this CLType
+    pub fn ts_type(self) -> &'static str {
+        match self {
+            ArgType::Unit => "void",
+            ArgType::Bool => "boolean",
+            ArgType::U8 | ArgType::U32 => "number",
+            ArgType::U64 | ArgType::U256 | ArgType::U512 => "bigint",
+            // Formatted strings: "uref-…", "hash-…", "account-hash-…", hex public key
+            ArgType::String
+            | ArgType::URef
+            | ArgType::Key
+            | ArgType::AccountHash
+            | ArgType::PublicKey => "string",
+            ArgType::Bytes | ArgType::Hash => "Uint8Array",
+            ArgType::U64Triple => "[bigint, bigint, bigint]",
+        }
+    }
+}
+
+/// Who may call an entry point
+///
+/// Roles are account or package hashes held in contract storage and checked
+/// by the entry point itself, so every variant registers as
+/// `EntryPointAccess::Public` on chain. The role is recorded here so the ABI
+/// tells clients who can call.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Access {
+    /// Anyone
+    Public,
+    /// The admin account only
+    Admin,
+    /// The sequencer account only
+    Sequencer,
+    /// The compliance account only
+    Compliance,
+    /// The bridge contract only, for companion contracts such as wCSPR
+    Bridge,
+}
+
+impl Access {
+    /// Name used in the JSON ABI
+    pub fn abi_name(self) -> &'static str {
+        match self {
+            Access::Public => "public",
+            Access::Admin => "admin",
+            Access::Sequencer => "sequencer",
+            Access::Compliance => "compliance",
+            Access::Bridge => "bridge",
+        }
+    }
+}
+
+/// Declaration of one named argument
+pub struct ArgDef {
+    pub name: &'static str,
+    pub ty: ArgType,
+    /// The entry point accepts the call without this argument
+    pub optional: bool,
+}
+
+/// Declaration of one entry point
+pub struct EntryPointDef {
+    pub name: &'static str,
+    pub args: &'static [ArgDef],
+    pub ret: ArgType,
+    pub access: Access,
+}
+
+/// Shorthand for building `ArgDef` tables
+pub const fn arg(name: &'static str, ty: ArgType) -> ArgDef {
+    ArgDef {
+        name,
+        ty,
+        optional: false,
+    }
+}
+
+/// An argument the caller may leave out
+pub const fn optional_arg(name: &'static str, ty: ArgType) -> ArgDef {
+    ArgDef {
+        name,
+        ty,
+        optional: true,
+    }
+}
+
+/// Build the `EntryPoints` registered at install
+pub fn entry_points(defs: &[EntryPointDef]) -> EntryPoints {
+    let mut entry_points = EntryPoints::new();
+    for def in defs {
+        let params = def
+            .args
+            .iter()
+            .map(|a| Parameter::new(a.name, a.ty.cl_type()))
+            .collect();
+        entry_points.add_entry_point(EntityEntryPoint::new(
+            def.name,
+            params,
+            def.ret.cl_type(),
+            EntryPointAccess::Public,
+            EntryPointType::Called,
+            EntryPointPayment::Caller,
+        ));
+    }
+    entry_points
+}
+
+/// Render TypeScript definitions: an `<Name>Args` interface per entry point,
+/// `EntryPointArgs` / `EntryPointReturns` maps keyed by entry point name and
+/// a `ContractError` enum of the user error codes
+pub fn to_typescript(defs: &[EntryPointDef], errors: &[Error]) -> String {
+    let mut out =
+        String::from("// Generated from the contract's entry point table. Do not edit.\n");
+    for def in defs {
+        out.push_str("\nexport interface ");
+        out.push_str(&pascal_case(def.name));
+        out.push_str("Args {\n");
+        for a in def.args {
+            out.push_str("  ");
+            out.push_str(a.name);
+            out.push_str(if a.optional { "?: " } else { ": " });
+            out.push_str(a.ty.ts_type());
+            out.push_str(";\n");
+        }
+        out.push_str("}\n");
+    }
+
+    out.push_str("\nexport interface EntryPointArgs {\n");
+    for def in defs {
+        out.push_str("  ");
+        out.push_str(def.name);
+        out.push_str(": ");
+        out.push_str(&pascal_case(def.name));
+        out.push_str("Args;\n");
+    }
+    out.push_str("}\n\nexport interface EntryPointReturns {\n");
+    for def in defs {
+        out.push_str("  ");
+        out.push_str(def.name);
+        out.push_str(": ");
+        out.push_str(def.ret.ts_type());
+        out.push_str(";\n");
+    }
+    out.push_str("}\n\nexport type EntryPointName = keyof EntryPointArgs;\n");
+
+    out.push_str("\n/** `ApiError::User` codes the contract reverts with */\n");
+    out.push_str("export declare const enum ContractError {\n");
+    for error in errors {
+        out.push_str(&format!(
+            "  /** {} */\n  {:?} = {},\n",
+            error.message(),
+            error,
+            error.code()
+        ));
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// `set_screening_mode` -> `SetScreeningMode`
+fn pascal_case(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    for part in name.split('_') {
+        let mut chars = part.chars();
+        if let Some(first) = chars.next() {
+            out.extend(first.to_uppercase());
+            out.push_str(chars.as_str());
+        }
+    }
+    out
+}
+
+/// Append `value` as a JSON string literal
+fn push_json_string(out: &mut String, value: &str) {
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                const HEX: &[u8; 16] = b"0123456789abcdef";
+                out.push_str("\\u00");
+                out.push(HEX[(c as usize) >> 4] as char);
+                out.push(HEX[(c as usize) & 0xf] as char);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Render the ABI as JSON:
+/// `{"entry_points":[{"name":..,"args":[{"name":..,"cl_type":..,"optional":..}],"ret":..,"access":..}],
+/// "errors":[{"code":..,"name":..,"message":..}]}`
+pub fn to_json(defs: &[EntryPointDef], errors: &[Error]) -> String {
+    let mut out = String::from("{\"entry_points\":[");
+    for (i, def) in defs.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        out.push_str("{\"name\":");
+        push_json_string(&mut out, def.name);
+        out.push_str(",\"args\":[");
+        for (j, a) in def.args.iter().enumerate() {
+            if j > 0 {
+                out.push(',');
+            }
+            out.push_str("{\"name\":");
+            push_json_string(&mut out, a.name);
+            out.push_str(",\"cl_type\":");
+            push_json_string(&mut out, a.ty.abi_name());
+            out.push_str(",\"optional\":");
+            out.push_str(if a.optional { "true" } else { "false" });
+            out.push('}');
+        }
+        out.push_str("],\"ret\":");
+        push_json_string(&mut out, def.ret.abi_name());
+        out.push_str(",\"access\":");
+        push_json_string(&mut out, def.access.abi_name());
+        out.push('}');
+    }
+    out.push_str("],\"errors\":[");
+    for (i, error) in errors.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        out.push_str(&format!("{{\"code\":{},\"name\":", error.code()));
+        push_json_string(&mut out, &format!("{:?}", error));
+        out.push_str(",\"message\":");
+        push_json_string(&mut out, error.message());
+        out.push('}');
+    }
+    out.push_str("]}");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEFS: &[EntryPointDef] = &[
+        EntryPointDef {
+            name: "set_thing",
+            args: &[
+                arg("account", ArgType::AccountHash),
+                optional_arg("limit", ArgType::U64),
+            ],
+            ret: ArgType::Unit,
+            access: Access::Public,
+        },
+        EntryPointDef {
+            name: "get_root",
+            args: &[],
+            ret: ArgType::Hash,
+            access: Access::Sequencer,
+        },
+    ];
+
+    #[test]
+    fn pascal_case_joins_snake_case_words() {
+        assert_eq!(pascal_case("set_screening_mode"), "SetScreeningMode");
+        assert_eq!(pascal_case("init"), "Init");
+        assert_eq!(pascal_case("a__b_"), "AB");
+        assert_eq!(pascal_case(""), "");
+    }
+
+    const ERRORS: &[Error] = &[Error::MissingKey, Error::Paused];
+
+    #[test]
+    fn json_lists_every_entry_point_and_argument() {
+        assert_eq!(
+            to_json(DEFS, &[]),
+            concat!(
+                r#"{"entry_points":["#,
+                r#"{"name":"set_thing","args":["#,
+                r#"{"name":"account","cl_type":"AccountHash","optional":false},"#,
+                r#"{"name":"limit","cl_type":"U64","optional":true}"#,
+                r#"],"ret":"Unit","access":"public"},"#,
+                r#"{"name":"get_root","args":[],"ret":"ByteArray32","access":"sequencer"}"#,
+                r#"],"errors":[]}"#,
+            )
+        );
+    }
+
+    #[test]
+    fn json_lists_error_codes() {
+        assert!(to_json(&[], ERRORS).ends_with(concat!(
+            r#""errors":["#,
+            r#"{"code":1,"name":"MissingKey","message":"contract is not initialized: "#,
+            r#"a required named key is missing"},"#,
+            r#"{"code":33,"name":"Paused","message":"bridge is paused"}]}"#,
+        )));
+    }
+
+    #[test]
+    fn json_strings_are_escaped() {
+        let mut out = String::new();
+        push_json_string(&mut out, "a\"b\\c\nd\u{1}");
+        assert_eq!(out, r#""a\"b\\c\nd\u0001""#);
+    }
+
+    #[test]
+    fn typescript_marks_optional_arguments() {
+        let ts = to_typescript(DEFS, &[]);
+        assert!(ts.contains(
+            "export interface SetThingArgs {\n  account: string;\n  limit?: bigint;\n}"
+        ));
+        assert!(ts.contains("  get_root: Uint8Array;\n"));
+    }
+
+    #[test]
+    fn typescript_enumerates_error_codes() {
+        let ts = to_typescript(&[], ERRORS);
+        assert!(ts.contains(
+            "export declare const enum ContractError {\n  /** contract is not initialized: \
+             a required named key is missing */\n  MissingKey = 1,\n  \
+             /** bridge is paused */\n  Paused = 33,\n}\n"
+        ));
+    }
+
+    #[test]
+    fn abi_names_are_distinct() {
+        let all = [
+            ArgType::Unit,
+            ArgType::Bool,
+            ArgType::U8,
+            ArgType::U32,
+            ArgType::U64,
+            ArgType::U256,
+            ArgType::U512,
+            ArgType::String,
+            ArgType::URef,
+            ArgType::Key,
+            ArgType::AccountHash,
+            ArgType::Bytes,
+            ArgType::Hash,
+            ArgType::U64Triple,
+        ];
+        for (i, a) in all.iter().enumerate() {
+            for b in &all[i + 1..] {
+                assert_ne!(a.abi_name(), b.abi_name(), "{:?} and {:?}", a, b);
+            }
+        }
+    }
+}
@@ -0,0 +1,44 @@
+//! Write the contract ABI as JSON and TypeScript definitions
+//!
+//! usage: cargo run --features abi-gen --bin abi_gen -- [out_dir]
+//!
+//! Both files are rendered from `entry_points::ENTRY_POINTS`, the same table
+//! the contract registers at install, and the shared error table the contract
+//! reverts with, so they cannot drift from the wasm.
+
+extern crate alloc;
+
+#[allow(dead_code)]
+mod abi;
+#[allow(dead_code)]
+mod entry_points;
+
+use std::{env, fs, path::PathBuf, process};
+
+use casper_accelerate_shared::error::Error;
+
+const DEFAULT_OUT_DIR: &str = "target/abi";
+const JSON_FILE: &str = "accelerate.abi.json";
+const TS_FILE: &str = "accelerate.d.ts";
+
+fn main() {
+    let out_dir = PathBuf::from(env::args().nth(1).unwrap_or_else(|| DEFAULT_OUT_DIR.into()));
+    if let Err(err) = write(&out_dir) {
+        eprintln!("abi_gen: {}: {}", out_dir.display(), err);
+        process::exit(1);
+    }
+}
+
+fn write(out_dir: &PathBuf) -> std::io::Result<()> {
+    fs::create_dir_all(out_dir)?;
+    fs::write(
+        out_dir.join(JSON_FILE),
+        abi::to_json(entry_points::ENTRY_POINTS, Error::ALL),
+    )?;
+    fs::write(
+        out_dir.join(TS_FILE),
+        abi::to_typescript(entry_points::ENTRY_POINTS, Error::ALL),
+    )?;
+    println!("wrote {} and {} to {}", JSON_FILE, TS_FILE, out_dir.display());
+    Ok(())
+}
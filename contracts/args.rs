@@ -0,0 +1,99 @@
+//! Borrowed decoding of large named arguments
+//!
+//! `runtime::get_named_arg::<Bytes>` copies the host buffer once into a `Vec`
+//! and then again while deserializing. For proofs and calldata in the 10-50KB
+//! range that doubles peak heap use, which wee_alloc never returns. `RawArg`
+//! keeps the single host buffer and hands out slices into it instead.
+
+use alloc::vec::Vec;
+use casper_contract::{contract_api::runtime, ext_ffi, unwrap_or_revert::UnwrapOrRevert};
+use casper_types::{
+    api_error,
+    bytesrepr::{self, FromBytes},
+    ApiError,
+};
+
+/// Serialized bytes of a named argument, exactly as passed by the host
+pub struct RawArg {
+    buf: Vec<u8>,
+}
+
+impl RawArg {
+    /// Read a named argument, reverting with `MissingArgument` if absent
+    pub fn get(name: &str) -> Self {
+        Self::try_get(name).unwrap_or_revert_with(ApiError::MissingArgument)
+    }
+
+    /// Read a named argument, returning `None` if it was not supplied
+    pub fn try_get(name: &str) -> Option<Self> {
+        let size = named_arg_size(name)?;
+        let mut buf = Vec::with_capacity(size);
+        if size > 0 {
+            let ret = unsafe {
+                ext_ffi::casper_get_named_arg(
+                    name.as_bytes().as_ptr(),
+                    name.len(),
+                    buf.as_mut_ptr(),
+                    size,
+                )
+            };
+            api_error::result_from(ret).unwrap_or_revert();
+            // Host has filled exactly `size` bytes
+            unsafe { buf.set_len(size) };
+        }
+        Some(RawArg { buf })
+    }
+
+    /// Borrow the payload of a `List<U8>` / `Bytes` argument without copying
+    pub fn as_bytes(&self) -> &[u8] {
+        borrow_bytes(&self.buf).unwrap_or_revert_with(ApiError::InvalidArgument)
+    }
+
+    /// Deserialize into an owned value, `None` if the bytes don't decode as `T`
+    pub fn try_parse<T: FromBytes>(&self) -> Option<T> {
+        match T::from_bytes(&self.buf) {
+            Ok((value, [])) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Deserialize into an owned value, for small arguments
+    pub fn parse<T: FromBytes>(&self) -> T {
+        let (value, rem) =
+            T::from_bytes(&self.buf).unwrap_or_revert_with(ApiError::InvalidArgument);
+        if !rem.is_empty() {
+            runtime::revert(ApiError::InvalidArgument);
+        }
+        value
+    }
+}
+
+/// Split a length-prefixed byte list into its payload, checking the length
+pub fn borrow_bytes(bytes: &[u8]) -> Result<&[u8], bytesrepr::Error> {
+    let (len, rem) = u32::from_bytes(bytes)?;
+    let len = len as usize;
+    if rem.len() != len {
+        return Err(if rem.len() < len {
+            bytesrepr::Error::EarlyEndOfStream
+        } else {
+            bytesrepr::Error::LeftOverBytes
+        });
+    }
+    Ok(rem)
+}
+
+fn named_arg_size(name: &str) -> Option<usize> {
+    let mut size: usize = 0;
+    let ret = unsafe {
+        ext_ffi::casper_get_named_arg_size(
+            name.as_bytes().as_ptr(),
+            name.len(),
+            &mut size as *mut usize,
+        )
+    };
+    match api_error::result_from(ret) {
+        Ok(()) => Some(size),
+        Err(ApiError::MissingArgument) => None,
+        Err(e) => runtime::revert(e),
+    }
+}
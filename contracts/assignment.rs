@@ -0,0 +1,98 @@
+//! Signed withdrawal assignments
+//!
+//! `claim_to` lets the recipient account of a pending withdrawal hand it to
+//! another purse or account, typically a fast-exit pool that pays them at
+//! once. The recipient signs `digest(chain_name, package, index,
+//! new_recipient)` off-chain. The domain tag, chain name, bridge package
+//! hash and queue index keep the signature from being replayed as another
+//! kind of message, on another network, on another deployment of the bridge
+//! on the same network, or for another withdrawal. A purse is signed without
+//! its access rights, since the caller passes it with whatever rights it
+//! holds.
+
+use alloc::vec::Vec;
+use casper_types::{
+    bytesrepr::{self, ToBytes},
+    Key,
+};
+
+use crate::hash;
+
+const DOMAIN: &[u8] = b"casper-accelerate/assign-withdrawal";
+
+/// What the recipient signs to assign withdrawal `index` of the bridge
+/// package `package` to `new_recipient`
+pub fn digest(
+    chain_name: &str,
+    package: [u8; 32],
+    index: u64,
+    new_recipient: &Key,
+) -> Result<[u8; 32], bytesrepr::Error> {
+    let new_recipient = match new_recipient {
+        Key::URef(purse) => Key::URef(purse.remove_access_rights()),
+        key => *key,
+    };
+    let mut preimage = Vec::with_capacity(
+        DOMAIN.len()
+            + chain_name.serialized_length()
+            + package.len()
+            + 8
+            + new_recipient.serialized_length(),
+    );
+    preimage.extend_from_slice(DOMAIN);
+    chain_name.write_bytes(&mut preimage)?;
+    preimage.extend_from_slice(&package);
+    index.write_bytes(&mut preimage)?;
+    new_recipient.write_bytes(&mut preimage)?;
+    Ok(hash::blake2b(preimage))
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::{account::AccountHash, crypto, AccessRights, PublicKey, SecretKey, URef};
+
+    use super::*;
+
+    fn purse(rights: AccessRights) -> Key {
+        Key::URef(URef::new([4; 32], rights))
+    }
+
+    const BRIDGE: [u8; 32] = [9; 32];
+
+    #[test]
+    fn binds_chain_index_and_recipient() {
+        let pool = purse(AccessRights::ADD);
+        let base = digest("casper-test", BRIDGE, 7, &pool).unwrap();
+        assert_ne!(base, digest("casper", BRIDGE, 7, &pool).unwrap());
+        assert_ne!(base, digest("casper-test", BRIDGE, 8, &pool).unwrap());
+        let account = Key::Account(AccountHash::new([4; 32]));
+        assert_ne!(base, digest("casper-test", BRIDGE, 7, &account).unwrap());
+    }
+
+    #[test]
+    fn signature_for_another_bridge_package_is_rejected() {
+        let signer = SecretKey::ed25519_from_bytes([5; 32]).unwrap();
+        let public_key = PublicKey::from(&signer);
+        let pool = purse(AccessRights::ADD);
+        let canary = digest("casper-test", [8; 32], 7, &pool).unwrap();
+        let signature = crypto::sign(canary, &signer, &public_key);
+
+        assert!(crypto::verify(canary, &signature, &public_key).is_ok());
+        let main = digest("casper-test", BRIDGE, 7, &pool).unwrap();
+        assert!(crypto::verify(main, &signature, &public_key).is_err());
+    }
+
+    #[test]
+    fn ignores_purse_access_rights() {
+        assert_eq!(
+            digest("casper-test", BRIDGE, 1, &purse(AccessRights::ADD)).unwrap(),
+            digest(
+                "casper-test",
+                BRIDGE,
+                1,
+                &purse(AccessRights::READ_ADD_WRITE)
+            )
+            .unwrap(),
+        );
+    }
+}
@@ -0,0 +1,99 @@
+//! Append-only audit log of privileged calls
+//!
+//! Every admin, guardian, compliance or sequencer action appends an entry
+//! and emits a `PrivilegedAction` message, so the operations trail can be
+//! rebuilt from chain data alone.
+
+use alloc::vec::Vec;
+use casper_contract::contract_api::runtime;
+use casper_types::{
+    account::AccountHash,
+    bytesrepr::{self, FromBytes, ToBytes},
+};
+
+use crate::{
+    envelope::{Envelope, Versioned},
+    events::{self, PrivilegedAction},
+    hash,
+    store::IndexedDict,
+    types::AuditAction,
+};
+
+const DICT_AUDIT_LOG: &str = "audit_log";
+
+/// One privileged call
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AuditEntry {
+    pub action: AuditAction,
+    pub caller: AccountHash,
+    /// blake2b-256 of the bytesrepr-encoded arguments
+    pub args_hash: [u8; 32],
+    /// Block time in ms
+    pub blocktime: u64,
+}
+
+impl Versioned for AuditEntry {
+    const VERSION: u8 = 1;
+
+    fn encoded_length(&self) -> usize {
+        self.action.serialized_length()
+            + self.caller.serialized_length()
+            + self.args_hash.serialized_length()
+            + self.blocktime.serialized_length()
+    }
+
+    fn encode(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        self.action.write_bytes(writer)?;
+        self.caller.write_bytes(writer)?;
+        self.args_hash.write_bytes(writer)?;
+        self.blocktime.write_bytes(writer)
+    }
+
+    fn decode(_version: u8, bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (action, rem) = AuditAction::from_bytes(bytes)?;
+        let (caller, rem) = AccountHash::from_bytes(rem)?;
+        let (args_hash, rem) = <[u8; 32]>::from_bytes(rem)?;
+        let (blocktime, rem) = u64::from_bytes(rem)?;
+        Ok((
+            AuditEntry {
+                action,
+                caller,
+                args_hash,
+                blocktime,
+            },
+            rem,
+        ))
+    }
+}
+
+fn log() -> IndexedDict<Envelope<AuditEntry>> {
+    IndexedDict::open(DICT_AUDIT_LOG)
+}
+
+pub fn init() {
+    IndexedDict::<Envelope<AuditEntry>>::create(DICT_AUDIT_LOG);
+}
+
+/// Verify log storage after install
+pub fn self_check() {
+    IndexedDict::<Envelope<AuditEntry>>::require(DICT_AUDIT_LOG);
+}
+
+/// Append an entry for the current caller and emit the matching event
+pub fn record(action: AuditAction, args: &[u8]) -> u64 {
+    let entry = AuditEntry {
+        action,
+        caller: runtime::get_caller(),
+        args_hash: hash::blake2b(args),
+        blocktime: u64::from(runtime::get_blocktime()),
+    };
+    let index = log().push(Envelope(entry.clone()));
+    events::emit(&PrivilegedAction {
+        index,
+        action: entry.action,
+        caller: entry.caller,
+        args_hash: entry.args_hash,
+        blocktime: entry.blocktime,
+    });
+    index
+}
@@ -0,0 +1,42 @@
+//! Per-batch metadata
+//!
+//! Every accepted batch gets a `BatchRecord` in the `batches` dictionary,
+//! indexed by batch number, so historical roots and who proposed them can be
+//! looked up when arguing about a specific batch. The record's layout lives
+//! in the shared crate so indexers decode what the contract writes.
+
+use casper_accelerate_shared::envelope::Envelope;
+
+pub use casper_accelerate_shared::batches::BatchRecord;
+
+use crate::store::IndexedDict;
+
+const DICT_BATCHES: &str = "batches";
+
+fn batches() -> IndexedDict<Envelope<BatchRecord>> {
+    IndexedDict::open(DICT_BATCHES)
+}
+
+pub fn init() {
+    IndexedDict::<Envelope<BatchRecord>>::create(DICT_BATCHES);
+}
+
+/// Verify batch storage after install
+pub fn self_check() {
+    IndexedDict::<Envelope<BatchRecord>>::require(DICT_BATCHES);
+}
+
+/// Number of batches with a stored record
+pub fn len() -> u64 {
+    batches().len()
+}
+
+/// Record the next batch and return its index
+pub fn record(record: BatchRecord) -> u64 {
+    batches().push(Envelope(record))
+}
+
+/// Record of batch `index`, if it has been accepted
+pub fn get(index: u64) -> Option<BatchRecord> {
+    batches().get(index).map(Envelope::into_inner)
+}
@@ -0,0 +1,201 @@
+//! Proving bounties
+//!
+//! The sequencer can post a reward for proving a batch it has built but not
+//! proven, escrowing it in the fee purse. Bounties are keyed by `batch_hash`
+//! of the batch's public inputs, so a third-party prover only needs the two
+//! roots and the forced-queue range to find the work. The first proof the
+//! verifier accepts for those inputs claims the whole reward; the proof is
+//! published in the `BountyClaimed` event for the sequencer to submit with
+//! the batch.
+//!
+//! A proof is not bound to whoever submits it, so claims go through
+//! commit-reveal: the prover first commits to `claim_commitment` of the
+//! batch, its own identity and the proof, and claims in a later block by
+//! revealing the proof. Someone who copies the proof from a pending claim
+//! has no earlier commitment under their own identity, and the bounty is
+//! paid to the immediate caller that revealed. A sequencer that proves the
+//! batch itself reclaims its own bounty the same way.
+
+use alloc::vec::Vec;
+use casper_contract::{
+    contract_api::{runtime, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    bytesrepr::{self, ToBytes},
+    Key, URef, U512,
+};
+
+use crate::{error::Error, hash, merkle, public_inputs::PublicInputs, store, store::TypedDict};
+
+pub const KEY_FEE_PURSE: &str = "fee_purse";
+const DICT_BOUNTIES: &str = "proving_bounties";
+const DICT_CLAIM_COMMITMENTS: &str = "bounty_claim_commitments";
+
+const DOMAIN: &[u8] = b"casper-accelerate/batch";
+const CLAIM_DOMAIN: &[u8] = b"casper-accelerate/bounty-claim";
+
+fn bounties() -> TypedDict<[u8; 32], U512> {
+    TypedDict::open(DICT_BOUNTIES)
+}
+
+/// Block height each claim commitment was first made at
+fn commitments() -> TypedDict<[u8; 32], u64> {
+    TypedDict::open(DICT_CLAIM_COMMITMENTS)
+}
+
+pub fn init() {
+    let fee_purse = system::create_purse();
+    runtime::put_key(KEY_FEE_PURSE, fee_purse.into());
+    TypedDict::<[u8; 32], U512>::create(DICT_BOUNTIES);
+    TypedDict::<[u8; 32], u64>::create(DICT_CLAIM_COMMITMENTS);
+}
+
+/// Verify bounty storage after install
+pub fn self_check() {
+    store::require_key(DICT_BOUNTIES);
+    store::require_key(DICT_CLAIM_COMMITMENTS);
+    system::get_purse_balance(fee_purse()).unwrap_or_revert_with(Error::SelfCheckFailed);
+}
+
+/// Purse holding posted rewards
+pub fn fee_purse() -> URef {
+    store::key_uref(KEY_FEE_PURSE)
+}
+
+/// Identifier of the batch with public inputs `inputs`, if both roots fit
+/// in 256 bits
+pub fn batch_hash(inputs: &PublicInputs) -> Option<[u8; 32]> {
+    let mut preimage = Vec::with_capacity(DOMAIN.len() + 128);
+    preimage.extend_from_slice(DOMAIN);
+    preimage.extend_from_slice(&merkle::root_bytes(inputs.prev_root)?);
+    preimage.extend_from_slice(&merkle::root_bytes(inputs.new_root)?);
+    preimage.extend_from_slice(&inputs.forced_acc_from);
+    preimage.extend_from_slice(&inputs.forced_acc_to);
+    Some(hash::blake2b(preimage))
+}
+
+/// Commitment to claiming the bounty for `batch_hash` as `prover` with
+/// `proof`, the serialized proof argument
+pub fn claim_commitment(
+    batch_hash: [u8; 32],
+    prover: &Key,
+    proof: &[u8],
+) -> Result<[u8; 32], bytesrepr::Error> {
+    let prover = prover.to_bytes()?;
+    let mut preimage = Vec::with_capacity(CLAIM_DOMAIN.len() + 32 + prover.len() + proof.len());
+    preimage.extend_from_slice(CLAIM_DOMAIN);
+    preimage.extend_from_slice(&batch_hash);
+    preimage.extend_from_slice(&prover);
+    preimage.extend_from_slice(proof);
+    Ok(hash::blake2b(preimage))
+}
+
+/// Record `commitment` at block `height`; committing again keeps the
+/// first height
+pub fn commit(commitment: [u8; 32], height: u64) {
+    if commitments().get(&commitment).is_none() {
+        commitments().set(&commitment, height);
+    }
+}
+
+/// Block height `commitment` was made at, if it was
+pub fn committed_at(commitment: [u8; 32]) -> Option<u64> {
+    commitments().get(&commitment)
+}
+
+/// A claim can be revealed at block `now` only if its commitment was made
+/// in an earlier block
+pub fn check_reveal(committed_at: Option<u64>, now: u64) -> Result<(), Error> {
+    match committed_at {
+        Some(height) if height < now => Ok(()),
+        _ => Err(Error::NoClaimCommitment),
+    }
+}
+
+/// Add `reward` to the bounty for `batch_hash` and return the new total
+pub fn post(batch_hash: [u8; 32], reward: U512) -> U512 {
+    let total = bounties()
+        .get(&batch_hash)
+        .unwrap_or_default()
+        .checked_add(reward)
+        .unwrap_or_revert_with(Error::InvalidAmount);
+    bounties().set(&batch_hash, total);
+    total
+}
+
+/// Remove the bounty for `batch_hash` and return its reward
+pub fn take(batch_hash: [u8; 32]) -> U512 {
+    let reward = bounties()
+        .get(&batch_hash)
+        .unwrap_or_revert_with(Error::NoBounty);
+    bounties().remove(&batch_hash);
+    reward
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::account::AccountHash;
+
+    use super::*;
+
+    fn inputs(prev_root: U512, new_root: U512) -> PublicInputs {
+        PublicInputs {
+            prev_root,
+            new_root,
+            forced_acc_from: [0; 32],
+            forced_acc_to: [1; 32],
+        }
+    }
+
+    #[test]
+    fn batch_hash_binds_both_roots_in_order() {
+        let (a, b) = (U512::from(1u64), U512::from(2u64));
+        let hash = batch_hash(&inputs(a, b)).unwrap();
+        assert_ne!(hash, batch_hash(&inputs(b, a)).unwrap());
+        assert_ne!(hash, batch_hash(&inputs(a, a)).unwrap());
+    }
+
+    #[test]
+    fn batch_hash_binds_the_forced_range() {
+        let (a, b) = (U512::from(1u64), U512::from(2u64));
+        let hash = batch_hash(&inputs(a, b)).unwrap();
+        let shorter = PublicInputs {
+            forced_acc_to: [0; 32],
+            ..inputs(a, b)
+        };
+        assert_ne!(hash, batch_hash(&shorter).unwrap());
+    }
+
+    #[test]
+    fn a_copied_proof_does_not_match_the_provers_commitment() {
+        let batch = [7; 32];
+        let prover = Key::Account(AccountHash::new([1; 32]));
+        let copier = Key::Account(AccountHash::new([2; 32]));
+        let proof = [3, 4, 5];
+        let committed = claim_commitment(batch, &prover, &proof).unwrap();
+        assert_ne!(committed, claim_commitment(batch, &copier, &proof).unwrap());
+        assert_ne!(
+            committed,
+            claim_commitment([8; 32], &prover, &proof).unwrap()
+        );
+        assert_ne!(
+            committed,
+            claim_commitment(batch, &prover, &[3, 4]).unwrap()
+        );
+    }
+
+    #[test]
+    fn a_claim_is_revealed_only_after_its_commitment() {
+        assert_eq!(check_reveal(Some(9), 10), Ok(()));
+        assert_eq!(check_reveal(Some(10), 10), Err(Error::NoClaimCommitment));
+        assert_eq!(check_reveal(None, 10), Err(Error::NoClaimCommitment));
+    }
+
+    #[test]
+    fn batch_hash_rejects_roots_wider_than_256_bits() {
+        let wide = U512::one() << 256;
+        assert!(batch_hash(&inputs(wide, U512::one())).is_none());
+        assert!(batch_hash(&inputs(U512::one(), wide)).is_none());
+    }
+}
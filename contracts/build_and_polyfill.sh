@@ -1,5 +1,11 @@
 #!/bin/bash
+# usage: ./contracts/build_and_polyfill.sh <testnet|mainnet|integration>
 export PATH=$HOME/.cargo/bin:$PATH
+NETWORK=$1
+case "$NETWORK" in
+  testnet|mainnet|integration) ;;
+  *) echo "usage: $0 <testnet|mainnet|integration>" >&2; exit 1 ;;
+esac
 # Paths relative to root
 TARGET_DIR=contracts/target/wasm32-unknown-unknown/release
 WASM_FILE=$TARGET_DIR/casper_accelerate_contract.wasm
@@ -9,7 +15,7 @@ FINAL_WASM=$TARGET_DIR/casper_accelerate_contract_final.wasm
 
 echo "Building contract (with bulk memory is fine)..."
 cd contracts
-cargo +nightly build --release --target wasm32-unknown-unknown -Z build-std=core,alloc,panic_abort --config 'profile.release.panic="abort"'
+cargo +nightly build --release --target wasm32-unknown-unknown --features "$NETWORK" -Z build-std=core,alloc,panic_abort --config 'profile.release.panic="abort"'
 cd ..
 
 echo "Converting to WAT..."
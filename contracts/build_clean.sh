@@ -1,22 +1,37 @@
 #!/bin/bash
+# usage: ./contracts/build_clean.sh <testnet|mainnet|integration>
 export PATH=$HOME/.cargo/bin:$PATH
+NETWORK=$1
+case "$NETWORK" in
+  testnet|mainnet|integration) ;;
+  *) echo "usage: $0 <testnet|mainnet|integration>" >&2; exit 1 ;;
+esac
 cd contracts
 
+WASM=target/wasm32-unknown-unknown/release/casper_accelerate_contract.wasm
+NETWORK_WASM=target/wasm32-unknown-unknown/release/casper_accelerate_contract.$NETWORK.wasm
+
 echo "Cleaning..."
 cargo +nightly clean
 
-echo "Building with build-std..."
+echo "Building with build-std for $NETWORK..."
 cargo +nightly build \
     --release \
     --target wasm32-unknown-unknown \
+    --no-default-features \
+    --features "$NETWORK" \
     -Z build-std=core,alloc,panic_abort \
     --config 'profile.release.panic="abort"'
 
 echo "Verifying..."
-if wasm2wat target/wasm32-unknown-unknown/release/casper_accelerate_contract.wasm | grep -qE "memory.copy|memory.fill"; then
+if wasm2wat $WASM | grep -qE "memory.copy|memory.fill"; then
   echo "Failed: Instructions still present"
   exit 1
 else
   echo "Success: Instructions removed"
-  ls -lh target/wasm32-unknown-unknown/release/casper_accelerate_contract.wasm
+  cp $WASM $NETWORK_WASM
+  ls -lh $NETWORK_WASM
 fi
+
+echo "Generating ABI artifacts..."
+cargo +nightly run --no-default-features --features "abi-gen $NETWORK" --bin abi_gen -- target/abi
@@ -0,0 +1,50 @@
+//! Identity of the immediate caller
+//!
+//! `runtime::get_caller` is the account that signed the deploy, however many
+//! contracts the call passed through on the way. Entry points that move a
+//! holder's value go by the immediate caller instead, so a contract the user
+//! happens to call cannot act for them. Contracts are identified by package
+//! hash, which survives upgrades.
+
+use casper_contract::contract_api::runtime;
+use casper_types::{account::AccountHash, contracts::ContractPackageHash, Key, PackageHash};
+
+use crate::error::Error;
+
+/// `CallerInfo` field holding an account's hash
+const CALLER_ACCOUNT: u8 = 0;
+/// `CallerInfo` field holding a Casper 2.0 entity's package hash
+const CALLER_PACKAGE: u8 = 1;
+/// `CallerInfo` field holding a legacy contract's package hash
+const CALLER_CONTRACT_PACKAGE: u8 = 2;
+
+/// Package hash of the contract that called this entry point, if any
+pub fn package() -> Option<[u8; 32]> {
+    let caller = runtime::get_immediate_caller().ok()?;
+    let legacy = caller
+        .get_field_by_index(CALLER_CONTRACT_PACKAGE)
+        .and_then(|value| value.to_t::<Option<ContractPackageHash>>().ok())
+        .flatten()
+        .map(|package| package.value());
+    legacy.or_else(|| {
+        caller
+            .get_field_by_index(CALLER_PACKAGE)?
+            .to_t::<Option<PackageHash>>()
+            .ok()?
+            .map(|package| package.value())
+    })
+}
+
+/// The immediate caller: `Key::Account` for an account, `Key::Hash` of its
+/// package hash for a contract
+pub fn immediate() -> Key {
+    let caller = runtime::get_immediate_caller().unwrap_or_else(|e| runtime::revert(e));
+    let account = caller
+        .get_field_by_index(CALLER_ACCOUNT)
+        .and_then(|value| value.to_t::<Option<AccountHash>>().ok())
+        .flatten();
+    match account {
+        Some(account) => Key::Account(account),
+        None => Key::Hash(package().unwrap_or_else(|| runtime::revert(Error::Unauthorized))),
+    }
+}
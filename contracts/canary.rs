@@ -0,0 +1,60 @@
+//! Canary mode for limited pilots of new contract versions
+//!
+//! Set once at install. A canary caps the value the contract purse may hold
+//! and stops taking deposits at an expiry block height, after which users
+//! can only withdraw. A regular install leaves both unset.
+
+use casper_contract::contract_api::runtime;
+use casper_types::U512;
+
+use crate::{error::Error, store};
+
+const KEY_CANARY_TVL_CAP: &str = "canary_tvl_cap";
+const KEY_CANARY_EXPIRY_HEIGHT: &str = "canary_expiry_height";
+
+pub fn init(tvl_cap: Option<U512>, expiry_height: Option<u64>) {
+    store::new_key(KEY_CANARY_TVL_CAP, tvl_cap);
+    store::new_key(KEY_CANARY_EXPIRY_HEIGHT, expiry_height);
+}
+
+/// Verify canary storage after install
+pub fn self_check() {
+    store::require_key(KEY_CANARY_TVL_CAP);
+    store::require_key(KEY_CANARY_EXPIRY_HEIGHT);
+}
+
+/// Revert once a canary install has passed its expiry height
+pub fn require_active() {
+    let expiry_height: Option<u64> = store::read_key(KEY_CANARY_EXPIRY_HEIGHT);
+    if let Some(expiry_height) = expiry_height {
+        if runtime::get_block_height() >= expiry_height {
+            runtime::revert(Error::CanaryExpired);
+        }
+    }
+}
+
+fn tvl_cap() -> Option<U512> {
+    store::read_key(KEY_CANARY_TVL_CAP)
+}
+
+/// Revert unless a deposit leaving `tvl` in the contract purse is allowed
+pub fn check_deposit(tvl: U512) {
+    require_active();
+    if let Some(tvl_cap) = tvl_cap() {
+        if tvl > tvl_cap {
+            runtime::revert(Error::CanaryCapExceeded);
+        }
+    }
+}
+
+/// Revert if a withdrawal request for `amount` exceeds what a canary
+/// install can ever hold
+///
+/// Withdrawals stay open after expiry, so only the cap applies.
+pub fn check_withdrawal(amount: U512) {
+    if let Some(tvl_cap) = tvl_cap() {
+        if amount > tvl_cap {
+            runtime::revert(Error::CanaryCapExceeded);
+        }
+    }
+}
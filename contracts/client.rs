@@ -1,29 +1,60 @@
 #![no_std]
 #![no_main]
+#![feature(core_intrinsics)]
+#![allow(internal_features)]
 
 #[cfg(not(target_arch = "wasm32"))]
 compile_error!("target arch should be wasm32: compile with '--target wasm32-unknown-unknown'");
 
-use casper_contract::contract_api::runtime;
-use casper_types::{runtime_args, Key, RuntimeArgs, URef, U512};
+extern crate alloc;
+
+#[global_allocator]
+static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
+
+#[panic_handler]
+fn panic(_info: &core::panic::PanicInfo) -> ! {
+    core::intrinsics::abort()
+}
+
+#[allow(dead_code)]
+mod args;
+#[allow(dead_code)]
+mod error;
+#[allow(dead_code)]
+mod session;
+
+use casper_contract::contract_api::{runtime, system};
+use casper_contract::unwrap_or_revert::UnwrapOrRevert;
+use casper_types::runtime_args;
+
+use error::Error;
+use session::{ARG_AMOUNT, ARG_L2_ADDRESS, ARG_PURSE};
 
 const ENTRY_POINT_DEPOSIT: &str = "deposit";
-const ARG_AMOUNT: &str = "amount";
-const ARG_PURSE: &str = "purse";
 
 #[no_mangle]
 pub extern "C" fn call() {
-    let contract_hash: Key = runtime::get_named_arg("contract_hash");
-    let amount: U512 = runtime::get_named_arg(ARG_AMOUNT);
-    let purse: URef = runtime::get_named_arg(ARG_PURSE);
+    // Validate every argument before touching any purse
+    let contract_package_hash = session::contract_package_hash();
+    let contract_version = session::contract_version();
+    let amount = session::amount();
+    let purse = session::purse();
+    let l2_address = session::l2_address();
+
+    let balance = system::get_purse_balance(purse).unwrap_or_revert_with(Error::InvalidPurse);
+    if balance < amount {
+        runtime::revert(Error::InsufficientBalance);
+    }
 
     // Call the deposit entry point to record the deposit on L2
-    runtime::call_contract::<()>(
-        contract_hash.into_hash().unwrap().into(),
+    runtime::call_versioned_contract::<()>(
+        contract_package_hash,
+        contract_version,
         ENTRY_POINT_DEPOSIT,
         runtime_args! {
             ARG_AMOUNT => amount,
             ARG_PURSE => purse,
+            ARG_L2_ADDRESS => l2_address,
         },
     );
 }
@@ -0,0 +1,160 @@
+//! Deterministic per-user deposit addresses
+//!
+//! A deposit ID is `blake2b(owner || l2_address || salt)`, where the owner is
+//! the account that registers it. Registering creates a dedicated purse
+//! whose add-only URef can be handed to exchanges and other third parties;
+//! `sweep_deposit` later moves whatever arrived into the bridge purse and
+//! credits it to the registered L2 address.
+//!
+//! The owner is screened at registration and recorded with the address, so
+//! whoever triggers a sweep, the deposit is screened and booked as the
+//! owner's. Including the owner in the ID keeps one account from claiming
+//! another's address first.
+
+use alloc::{string::String, vec::Vec};
+use casper_contract::{
+    contract_api::{runtime, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    account::AccountHash,
+    bytesrepr::{self, FromBytes, ToBytes},
+    URef, U512,
+};
+
+use crate::{
+    envelope::{Envelope, Versioned},
+    error::Error,
+    hash,
+    store::TypedDict,
+};
+
+const DICT_DEPOSIT_ADDRESSES: &str = "deposit_addresses";
+
+/// Owner, purse and L2 account behind a deposit ID
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DepositAddress {
+    /// Account that registered the address; deposits are booked as its own
+    pub owner: AccountHash,
+    pub l2_address: String,
+    pub purse: URef,
+}
+
+impl Versioned for DepositAddress {
+    const VERSION: u8 = 1;
+
+    fn encoded_length(&self) -> usize {
+        self.owner.serialized_length()
+            + self.l2_address.serialized_length()
+            + self.purse.serialized_length()
+    }
+
+    fn encode(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        self.owner.write_bytes(writer)?;
+        self.l2_address.write_bytes(writer)?;
+        self.purse.write_bytes(writer)
+    }
+
+    fn decode(_version: u8, bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (owner, rem) = AccountHash::from_bytes(bytes)?;
+        let (l2_address, rem) = String::from_bytes(rem)?;
+        let (purse, rem) = URef::from_bytes(rem)?;
+        Ok((
+            DepositAddress {
+                owner,
+                l2_address,
+                purse,
+            },
+            rem,
+        ))
+    }
+}
+
+fn addresses() -> TypedDict<[u8; 32], Envelope<DepositAddress>> {
+    TypedDict::open(DICT_DEPOSIT_ADDRESSES)
+}
+
+pub fn init() {
+    TypedDict::<[u8; 32], Envelope<DepositAddress>>::create(DICT_DEPOSIT_ADDRESSES);
+}
+
+/// Verify deposit address storage after install
+pub fn self_check() {
+    crate::store::require_key(DICT_DEPOSIT_ADDRESSES);
+}
+
+/// Deposit ID for `owner`'s address crediting `l2_address` under `salt`
+pub fn deposit_id(
+    owner: &AccountHash,
+    l2_address: &str,
+    salt: u64,
+) -> Result<[u8; 32], bytesrepr::Error> {
+    let mut preimage = owner.to_bytes()?;
+    l2_address.write_bytes(&mut preimage)?;
+    salt.write_bytes(&mut preimage)?;
+    Ok(hash::blake2b(preimage))
+}
+
+/// Register (or look up) `owner`'s deposit address for `l2_address` and
+/// `salt`, returning its ID and an add-only URef to its purse
+pub fn register(owner: AccountHash, l2_address: String, salt: u64) -> ([u8; 32], URef) {
+    let id = deposit_id(&owner, &l2_address, salt).unwrap_or_revert_with(Error::Serialization);
+    let addresses = addresses();
+    let purse = match addresses.get(&id) {
+        Some(Envelope(existing)) => existing.purse,
+        None => {
+            let purse = system::create_purse();
+            addresses.set(
+                &id,
+                Envelope(DepositAddress {
+                    owner,
+                    l2_address,
+                    purse,
+                }),
+            );
+            purse
+        }
+    };
+    (id, purse.into_add())
+}
+
+/// Drain a deposit address into `target`, returning its owner, L2 address
+/// and the amount
+pub fn sweep(id: &[u8; 32], target: URef) -> (AccountHash, String, U512) {
+    let Envelope(address) = addresses()
+        .get(id)
+        .unwrap_or_revert_with(Error::UnknownDepositAddress);
+    let balance =
+        system::get_purse_balance(address.purse).unwrap_or_revert_with(Error::InvalidPurse);
+    if balance.is_zero() {
+        runtime::revert(Error::NothingToSweep);
+    }
+    system::transfer_from_purse_to_purse(address.purse, target, balance, None)
+        .unwrap_or_revert_with(Error::DepositTransferFailed);
+    (address.owner, address.l2_address, balance)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deposit_ids_belong_to_their_owner() {
+        let (alice, bob) = (AccountHash::new([1; 32]), AccountHash::new([2; 32]));
+        let id = deposit_id(&alice, "l2-alice", 0).unwrap();
+        assert_ne!(id, deposit_id(&bob, "l2-alice", 0).unwrap());
+        assert_ne!(id, deposit_id(&alice, "l2-alice", 1).unwrap());
+        assert_ne!(id, deposit_id(&alice, "l2-bob", 0).unwrap());
+    }
+
+    #[test]
+    fn record_round_trips_with_its_owner() {
+        let record = Envelope(DepositAddress {
+            owner: AccountHash::new([1; 32]),
+            l2_address: String::from("l2-alice"),
+            purse: URef::new([3; 32], casper_types::AccessRights::READ_ADD_WRITE),
+        });
+        let bytes = record.to_bytes().unwrap();
+        assert_eq!(Envelope::from_bytes(&bytes).unwrap(), (record, &[][..]));
+    }
+}
@@ -0,0 +1,67 @@
+//! Sequencer endpoint discovery
+//!
+//! The sequencer publishes where and how to reach it under the
+//! `endpoint_metadata` named key, so wallets can find the RPC endpoint from
+//! the contract instead of a hardcoded URL.
+
+use alloc::{string::String, vec::Vec};
+use casper_contract::contract_api::runtime;
+use casper_types::bytesrepr::{self, Bytes, FromBytes, ToBytes};
+
+use crate::{
+    envelope::{Envelope, Versioned},
+    store,
+};
+
+pub const KEY_ENDPOINT_METADATA: &str = "endpoint_metadata";
+
+/// How to reach the active sequencer
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EndpointMetadata {
+    pub rpc_url: String,
+    /// Key the sequencer signs L2 responses with
+    pub public_key: Bytes,
+    /// Highest RPC API version the endpoint serves
+    pub api_version: u32,
+}
+
+impl Versioned for EndpointMetadata {
+    const VERSION: u8 = 1;
+
+    fn encoded_length(&self) -> usize {
+        self.rpc_url.serialized_length()
+            + self.public_key.serialized_length()
+            + self.api_version.serialized_length()
+    }
+
+    fn encode(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        self.rpc_url.write_bytes(writer)?;
+        self.public_key.write_bytes(writer)?;
+        self.api_version.write_bytes(writer)
+    }
+
+    fn decode(_version: u8, bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (rpc_url, rem) = String::from_bytes(bytes)?;
+        let (public_key, rem) = Bytes::from_bytes(rem)?;
+        let (api_version, rem) = u32::from_bytes(rem)?;
+        Ok((
+            EndpointMetadata {
+                rpc_url,
+                public_key,
+                api_version,
+            },
+            rem,
+        ))
+    }
+}
+
+/// Publish or replace the endpoint metadata
+///
+/// The named key only exists once something has been published.
+pub fn set(metadata: EndpointMetadata) {
+    if runtime::has_key(KEY_ENDPOINT_METADATA) {
+        store::write_key(KEY_ENDPOINT_METADATA, Envelope(metadata));
+    } else {
+        store::new_key(KEY_ENDPOINT_METADATA, Envelope(metadata));
+    }
+}
@@ -0,0 +1,422 @@
+//! Entry point and argument names, and the table declaring them
+//!
+//! Kept free of host calls so `abi_gen` can render the same table into the
+//! JSON ABI and TypeScript definitions at build time.
+
+use crate::abi::{arg, optional_arg, Access, ArgType, EntryPointDef};
+
+// Entry point names
+pub const EP_INIT: &str = "init";
+pub const EP_SUBMIT_BATCH: &str = "submit_batch";
+pub const EP_DEPOSIT: &str = "deposit";
+pub const EP_WITHDRAW: &str = "withdraw";
+pub const EP_GET_STATE: &str = "get_state";
+pub const EP_SET_SCREENING_MODE: &str = "set_screening_mode";
+pub const EP_SET_SCREENED: &str = "set_screened";
+pub const EP_SET_COMPLIANCE: &str = "set_compliance";
+pub const EP_GET_BATCH_TIME: &str = "get_batch_time";
+pub const EP_REQUEST_L2_WITHDRAWAL: &str = "request_l2_withdrawal";
+pub const EP_REGISTER_DEPOSIT_ADDRESS: &str = "register_deposit_address";
+pub const EP_SWEEP_DEPOSIT: &str = "sweep_deposit";
+pub const EP_DEPOSIT_WITH_KEY: &str = "deposit_with_key";
+pub const EP_WITHDRAW_TO_ACCOUNT: &str = "withdraw_to_account";
+pub const EP_SELF_CHECK: &str = "self_check";
+pub const EP_GET_STATS: &str = "get_stats";
+pub const EP_GET_PROOF_STATS: &str = "get_proof_stats";
+pub const EP_SET_ENDPOINT_METADATA: &str = "set_endpoint_metadata";
+pub const EP_SET_SEQUENCER: &str = "set_sequencer";
+pub const EP_PAUSE: &str = "pause";
+pub const EP_UNPAUSE: &str = "unpause";
+pub const EP_FINALIZE_WITHDRAWAL: &str = "finalize_withdrawal";
+pub const EP_DISPUTE_ROOT: &str = "dispute_root";
+pub const EP_CANCEL_WITHDRAWAL: &str = "cancel_withdrawal";
+pub const EP_EXPIRE_WITHDRAWAL: &str = "expire_withdrawal";
+pub const EP_REACTIVATE_WITHDRAWAL: &str = "reactivate_withdrawal";
+pub const EP_SET_WITHDRAWAL_EXPIRY: &str = "set_withdrawal_expiry";
+pub const EP_WRAP_WITHDRAWAL: &str = "wrap_withdrawal";
+pub const EP_REDEEM_WCSPR: &str = "redeem_wcspr";
+pub const EP_SET_WCSPR_TOKEN: &str = "set_wcspr_token";
+pub const EP_CLAIM_TO: &str = "claim_to";
+pub const EP_GET_WITHDRAWAL_STATUS: &str = "get_withdrawal_status";
+pub const EP_SET_VERIFICATION_KEY: &str = "set_verification_key";
+pub const EP_SET_PROOF_SYSTEM: &str = "set_proof_system";
+pub const EP_POST_PROVING_BOUNTY: &str = "post_proving_bounty";
+pub const EP_COMMIT_BOUNTY_CLAIM: &str = "commit_bounty_claim";
+pub const EP_CLAIM_BOUNTY: &str = "claim_bounty";
+
+// Argument names
+pub const ARG_ROOT: &str = "root";
+pub const ARG_PROOF: &str = "proof";
+pub const ARG_NONCE: &str = "nonce";
+pub const ARG_WITHDRAWAL_INDEX: &str = "withdrawal_index";
+pub const ARG_AMOUNT: &str = "amount";
+pub const ARG_PURSE: &str = "purse";
+pub const ARG_L2_ADDRESS: &str = "l2_address";
+pub const ARG_RECIPIENT: &str = "recipient";
+pub const ARG_MODE: &str = "mode";
+pub const ARG_ACCOUNT: &str = "account";
+pub const ARG_LISTED: &str = "listed";
+pub const ARG_BATCH_INDEX: &str = "batch_index";
+pub const ARG_FORCED_FROM: &str = "forced_from";
+pub const ARG_FORCED_TO: &str = "forced_to";
+pub const ARG_TX_COUNT: &str = "tx_count";
+pub const ARG_SALT: &str = "salt";
+pub const ARG_DEPOSIT_ID: &str = "deposit_id";
+pub const ARG_L2_PUBLIC_KEY: &str = "l2_public_key";
+pub const ARG_ENTRY_POINT: &str = "entry_point";
+pub const ARG_RPC_URL: &str = "rpc_url";
+pub const ARG_PUBLIC_KEY: &str = "public_key";
+pub const ARG_API_VERSION: &str = "api_version";
+pub const ARG_VERIFYING_KEY: &str = "verifying_key";
+pub const ARG_CANARY_TVL_CAP: &str = "canary_tvl_cap";
+pub const ARG_CANARY_EXPIRY_HEIGHT: &str = "canary_expiry_height";
+pub const ARG_EXPIRY_WINDOW: &str = "expiry_window";
+pub const ARG_TOKEN: &str = "token";
+pub const ARG_SIGNATURE: &str = "signature";
+pub const ARG_CIRCUIT_ID: &str = "circuit_id";
+pub const ARG_PROOF_SYSTEM: &str = "proof_system";
+pub const ARG_ACCEPTED: &str = "accepted";
+pub const ARG_BATCH_HASH: &str = "batch_hash";
+pub const ARG_REWARD: &str = "reward";
+pub const ARG_COMMITMENT: &str = "commitment";
+pub const ARG_PREV_ROOT: &str = "prev_root";
+pub const ARG_CONTRACT_PACKAGE_HASH: &str = "contract_package_hash";
+
+/// Entry point declarations - the single source for registration and the ABI
+pub const ENTRY_POINTS: &[EntryPointDef] = &[
+    // init(verifying_key: Bytes, circuit_id: Hash, contract_package_hash: Key,
+    //      canary_tvl_cap?: U512, canary_expiry_height?: u64)
+    // - called once by the installer; the canary arguments are optional
+    EntryPointDef {
+        name: EP_INIT,
+        args: &[
+            arg(ARG_VERIFYING_KEY, ArgType::Bytes),
+            arg(ARG_CIRCUIT_ID, ArgType::Hash),
+            arg(ARG_CONTRACT_PACKAGE_HASH, ArgType::Key),
+            optional_arg(ARG_CANARY_TVL_CAP, ArgType::U512),
+            optional_arg(ARG_CANARY_EXPIRY_HEIGHT, ArgType::U64),
+        ],
+        ret: ArgType::Unit,
+        access: Access::Public,
+    },
+    // deposit(amount: U512, purse: URef, l2_address: String)
+    EntryPointDef {
+        name: EP_DEPOSIT,
+        args: &[
+            arg(ARG_AMOUNT, ArgType::U512),
+            arg(ARG_PURSE, ArgType::URef),
+            arg(ARG_L2_ADDRESS, ArgType::String),
+        ],
+        ret: ArgType::Unit,
+        access: Access::Public,
+    },
+    // deposit_with_key(amount: U512, purse: URef, l2_address: String, l2_public_key: Bytes)
+    EntryPointDef {
+        name: EP_DEPOSIT_WITH_KEY,
+        args: &[
+            arg(ARG_AMOUNT, ArgType::U512),
+            arg(ARG_PURSE, ArgType::URef),
+            arg(ARG_L2_ADDRESS, ArgType::String),
+            arg(ARG_L2_PUBLIC_KEY, ArgType::Bytes),
+        ],
+        ret: ArgType::Unit,
+        access: Access::Public,
+    },
+    // withdraw(amount: U512, nonce: u64, proof: Bytes, recipient: URef) -> withdrawal index
+    EntryPointDef {
+        name: EP_WITHDRAW,
+        args: &[
+            arg(ARG_AMOUNT, ArgType::U512),
+            arg(ARG_NONCE, ArgType::U64),
+            arg(ARG_PROOF, ArgType::Bytes),
+            arg(ARG_RECIPIENT, ArgType::URef),
+        ],
+        ret: ArgType::U64,
+        access: Access::Public,
+    },
+    // withdraw_to_account(amount: U512, nonce: u64, proof: Bytes, recipient: AccountHash)
+    // -> withdrawal index
+    EntryPointDef {
+        name: EP_WITHDRAW_TO_ACCOUNT,
+        args: &[
+            arg(ARG_AMOUNT, ArgType::U512),
+            arg(ARG_NONCE, ArgType::U64),
+            arg(ARG_PROOF, ArgType::Bytes),
+            arg(ARG_RECIPIENT, ArgType::AccountHash),
+        ],
+        ret: ArgType::U64,
+        access: Access::Public,
+    },
+    // finalize_withdrawal(withdrawal_index: u64)
+    EntryPointDef {
+        name: EP_FINALIZE_WITHDRAWAL,
+        args: &[arg(ARG_WITHDRAWAL_INDEX, ArgType::U64)],
+        ret: ArgType::Unit,
+        access: Access::Public,
+    },
+    // dispute_root(root: U512)
+    EntryPointDef {
+        name: EP_DISPUTE_ROOT,
+        args: &[arg(ARG_ROOT, ArgType::U512)],
+        ret: ArgType::Unit,
+        access: Access::Admin,
+    },
+    // cancel_withdrawal(withdrawal_index: u64)
+    EntryPointDef {
+        name: EP_CANCEL_WITHDRAWAL,
+        args: &[arg(ARG_WITHDRAWAL_INDEX, ArgType::U64)],
+        ret: ArgType::Unit,
+        access: Access::Public,
+    },
+    // expire_withdrawal(withdrawal_index: u64)
+    EntryPointDef {
+        name: EP_EXPIRE_WITHDRAWAL,
+        args: &[arg(ARG_WITHDRAWAL_INDEX, ArgType::U64)],
+        ret: ArgType::Unit,
+        access: Access::Public,
+    },
+    // reactivate_withdrawal(withdrawal_index: u64)
+    EntryPointDef {
+        name: EP_REACTIVATE_WITHDRAWAL,
+        args: &[arg(ARG_WITHDRAWAL_INDEX, ArgType::U64)],
+        ret: ArgType::Unit,
+        access: Access::Public,
+    },
+    // submit_batch(root: U512, proof: Bytes, forced_from: u64, forced_to: u64, tx_count: u64,
+    //              proof_system?: u8)
+    // - proof_system defaults to Groth16 over BN254
+    EntryPointDef {
+        name: EP_SUBMIT_BATCH,
+        args: &[
+            arg(ARG_ROOT, ArgType::U512),
+            arg(ARG_PROOF, ArgType::Bytes),
+            arg(ARG_FORCED_FROM, ArgType::U64),
+            arg(ARG_FORCED_TO, ArgType::U64),
+            arg(ARG_TX_COUNT, ArgType::U64),
+            optional_arg(ARG_PROOF_SYSTEM, ArgType::U8),
+        ],
+        ret: ArgType::Unit,
+        access: Access::Sequencer,
+    },
+    // post_proving_bounty(batch_hash: Hash, reward: U512, purse: URef)
+    EntryPointDef {
+        name: EP_POST_PROVING_BOUNTY,
+        args: &[
+            arg(ARG_BATCH_HASH, ArgType::Hash),
+            arg(ARG_REWARD, ArgType::U512),
+            arg(ARG_PURSE, ArgType::URef),
+        ],
+        ret: ArgType::Unit,
+        access: Access::Sequencer,
+    },
+    // commit_bounty_claim(commitment: Hash)
+    EntryPointDef {
+        name: EP_COMMIT_BOUNTY_CLAIM,
+        args: &[arg(ARG_COMMITMENT, ArgType::Hash)],
+        ret: ArgType::Unit,
+        access: Access::Public,
+    },
+    // claim_bounty(prev_root: U512, root: U512, forced_from: u64, forced_to: u64,
+    //              proof: Bytes, proof_system?: u8, purse?: URef) -> U512
+    EntryPointDef {
+        name: EP_CLAIM_BOUNTY,
+        args: &[
+            arg(ARG_PREV_ROOT, ArgType::U512),
+            arg(ARG_ROOT, ArgType::U512),
+            arg(ARG_FORCED_FROM, ArgType::U64),
+            arg(ARG_FORCED_TO, ArgType::U64),
+            arg(ARG_PROOF, ArgType::Bytes),
+            optional_arg(ARG_PROOF_SYSTEM, ArgType::U8),
+            optional_arg(ARG_PURSE, ArgType::URef),
+        ],
+        ret: ArgType::U512,
+        access: Access::Public,
+    },
+    // get_state() - read-only
+    EntryPointDef {
+        name: EP_GET_STATE,
+        args: &[],
+        ret: ArgType::Unit,
+        access: Access::Public,
+    },
+    // self_check() - read-only, reverts on an inconsistent install
+    EntryPointDef {
+        name: EP_SELF_CHECK,
+        args: &[],
+        ret: ArgType::Unit,
+        access: Access::Public,
+    },
+    // request_l2_withdrawal(l2_address: String, amount: U512), by the address's registrant
+    EntryPointDef {
+        name: EP_REQUEST_L2_WITHDRAWAL,
+        args: &[
+            arg(ARG_L2_ADDRESS, ArgType::String),
+            arg(ARG_AMOUNT, ArgType::U512),
+        ],
+        ret: ArgType::Unit,
+        access: Access::Public,
+    },
+    // register_deposit_address(l2_address: String, salt: u64) -> URef
+    EntryPointDef {
+        name: EP_REGISTER_DEPOSIT_ADDRESS,
+        args: &[arg(ARG_L2_ADDRESS, ArgType::String), arg(ARG_SALT, ArgType::U64)],
+        ret: ArgType::URef,
+        access: Access::Public,
+    },
+    // sweep_deposit(deposit_id: [u8; 32])
+    EntryPointDef {
+        name: EP_SWEEP_DEPOSIT,
+        args: &[arg(ARG_DEPOSIT_ID, ArgType::Hash)],
+        ret: ArgType::Unit,
+        access: Access::Public,
+    },
+    // get_batch_time(batch_index: u64) -> u64
+    EntryPointDef {
+        name: EP_GET_BATCH_TIME,
+        args: &[arg(ARG_BATCH_INDEX, ArgType::U64)],
+        ret: ArgType::U64,
+        access: Access::Public,
+    },
+    // get_stats(entry_point: String) -> u64
+    EntryPointDef {
+        name: EP_GET_STATS,
+        args: &[arg(ARG_ENTRY_POINT, ArgType::String)],
+        ret: ArgType::U64,
+        access: Access::Public,
+    },
+    // get_proof_stats() -> (count: u64, total_bytes: u64, max_bytes: u64)
+    EntryPointDef {
+        name: EP_GET_PROOF_STATS,
+        args: &[],
+        ret: ArgType::U64Triple,
+        access: Access::Public,
+    },
+    // set_screening_mode(mode: u8)
+    EntryPointDef {
+        name: EP_SET_SCREENING_MODE,
+        args: &[arg(ARG_MODE, ArgType::U8)],
+        ret: ArgType::Unit,
+        access: Access::Compliance,
+    },
+    // set_screened(account: AccountHash, listed: bool)
+    EntryPointDef {
+        name: EP_SET_SCREENED,
+        args: &[
+            arg(ARG_ACCOUNT, ArgType::AccountHash),
+            arg(ARG_LISTED, ArgType::Bool),
+        ],
+        ret: ArgType::Unit,
+        access: Access::Compliance,
+    },
+    // set_compliance(account: AccountHash)
+    EntryPointDef {
+        name: EP_SET_COMPLIANCE,
+        args: &[arg(ARG_ACCOUNT, ArgType::AccountHash)],
+        ret: ArgType::Unit,
+        access: Access::Compliance,
+    },
+    // pause()
+    EntryPointDef {
+        name: EP_PAUSE,
+        args: &[],
+        ret: ArgType::Unit,
+        access: Access::Admin,
+    },
+    // unpause()
+    EntryPointDef {
+        name: EP_UNPAUSE,
+        args: &[],
+        ret: ArgType::Unit,
+        access: Access::Admin,
+    },
+    // set_sequencer(account: AccountHash)
+    EntryPointDef {
+        name: EP_SET_SEQUENCER,
+        args: &[arg(ARG_ACCOUNT, ArgType::AccountHash)],
+        ret: ArgType::Unit,
+        access: Access::Admin,
+    },
+    // claim_to(withdrawal_index: u64, recipient: Key, public_key: PublicKey, signature: Bytes)
+    //     -> U512
+    EntryPointDef {
+        name: EP_CLAIM_TO,
+        args: &[
+            arg(ARG_WITHDRAWAL_INDEX, ArgType::U64),
+            arg(ARG_RECIPIENT, ArgType::Key),
+            arg(ARG_PUBLIC_KEY, ArgType::PublicKey),
+            arg(ARG_SIGNATURE, ArgType::Bytes),
+        ],
+        ret: ArgType::U512,
+        access: Access::Public,
+    },
+    // get_withdrawal_status(withdrawal_index: u64) -> u8
+    EntryPointDef {
+        name: EP_GET_WITHDRAWAL_STATUS,
+        args: &[arg(ARG_WITHDRAWAL_INDEX, ArgType::U64)],
+        ret: ArgType::U8,
+        access: Access::Public,
+    },
+    // wrap_withdrawal(withdrawal_index: u64)
+    EntryPointDef {
+        name: EP_WRAP_WITHDRAWAL,
+        args: &[arg(ARG_WITHDRAWAL_INDEX, ArgType::U64)],
+        ret: ArgType::Unit,
+        access: Access::Public,
+    },
+    // redeem_wcspr(withdrawal_index: u64, amount: U512, purse: Option<URef>)
+    EntryPointDef {
+        name: EP_REDEEM_WCSPR,
+        args: &[
+            arg(ARG_WITHDRAWAL_INDEX, ArgType::U64),
+            arg(ARG_AMOUNT, ArgType::U512),
+            optional_arg(ARG_PURSE, ArgType::URef),
+        ],
+        ret: ArgType::Unit,
+        access: Access::Public,
+    },
+    // set_wcspr_token(token: Key)
+    EntryPointDef {
+        name: EP_SET_WCSPR_TOKEN,
+        args: &[arg(ARG_TOKEN, ArgType::Key)],
+        ret: ArgType::Unit,
+        access: Access::Admin,
+    },
+    // set_verification_key(verifying_key: Bytes, circuit_id: Hash)
+    EntryPointDef {
+        name: EP_SET_VERIFICATION_KEY,
+        args: &[
+            arg(ARG_VERIFYING_KEY, ArgType::Bytes),
+            arg(ARG_CIRCUIT_ID, ArgType::Hash),
+        ],
+        ret: ArgType::Unit,
+        access: Access::Admin,
+    },
+    // set_proof_system(proof_system: u8, accepted: bool)
+    EntryPointDef {
+        name: EP_SET_PROOF_SYSTEM,
+        args: &[
+            arg(ARG_PROOF_SYSTEM, ArgType::U8),
+            arg(ARG_ACCEPTED, ArgType::Bool),
+        ],
+        ret: ArgType::Unit,
+        access: Access::Admin,
+    },
+    // set_withdrawal_expiry(expiry_window: u64)
+    EntryPointDef {
+        name: EP_SET_WITHDRAWAL_EXPIRY,
+        args: &[arg(ARG_EXPIRY_WINDOW, ArgType::U64)],
+        ret: ArgType::Unit,
+        access: Access::Admin,
+    },
+    // set_endpoint_metadata(rpc_url: String, public_key: Bytes, api_version: u32)
+    EntryPointDef {
+        name: EP_SET_ENDPOINT_METADATA,
+        args: &[
+            arg(ARG_RPC_URL, ArgType::String),
+            arg(ARG_PUBLIC_KEY, ArgType::Bytes),
+            arg(ARG_API_VERSION, ArgType::U32),
+        ],
+        ret: ArgType::Unit,
+        access: Access::Sequencer,
+    },
+];
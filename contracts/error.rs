@@ -0,0 +1,6 @@
+//! Contract error codes
+//!
+//! The table lives in the shared crate so client tooling reads the same
+//! codes and messages the contract reverts with.
+
+pub use casper_accelerate_shared::error::*;
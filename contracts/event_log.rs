@@ -0,0 +1,85 @@
+//! On-chain index of every emitted message
+//!
+//! Each `events::emit` appends an entry, so an indexer that starts late or
+//! drops its SSE stream can walk `event_log` from its last seen index and
+//! check each message it fetches against the recorded payload hash.
+
+use alloc::{string::String, vec::Vec};
+use casper_contract::contract_api::runtime;
+use casper_types::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    contract_messages::MessagePayload,
+};
+
+use crate::{
+    envelope::{Envelope, Versioned},
+    hash,
+    store::IndexedDict,
+};
+
+const DICT_EVENT_LOG: &str = "event_log";
+
+/// Where and when one message was emitted
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EventRecord {
+    pub topic: String,
+    /// blake2b-256 of the payload bytes (the bytesrepr-encoded event)
+    pub payload_hash: [u8; 32],
+    pub block_height: u64,
+}
+
+impl Versioned for EventRecord {
+    const VERSION: u8 = 1;
+
+    fn encoded_length(&self) -> usize {
+        self.topic.serialized_length()
+            + self.payload_hash.serialized_length()
+            + self.block_height.serialized_length()
+    }
+
+    fn encode(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        self.topic.write_bytes(writer)?;
+        self.payload_hash.write_bytes(writer)?;
+        self.block_height.write_bytes(writer)
+    }
+
+    fn decode(_version: u8, bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (topic, rem) = String::from_bytes(bytes)?;
+        let (payload_hash, rem) = <[u8; 32]>::from_bytes(rem)?;
+        let (block_height, rem) = u64::from_bytes(rem)?;
+        Ok((
+            EventRecord {
+                topic,
+                payload_hash,
+                block_height,
+            },
+            rem,
+        ))
+    }
+}
+
+fn log() -> IndexedDict<Envelope<EventRecord>> {
+    IndexedDict::open(DICT_EVENT_LOG)
+}
+
+pub fn init() {
+    IndexedDict::<Envelope<EventRecord>>::create(DICT_EVENT_LOG);
+}
+
+/// Verify index storage after install
+pub fn self_check() {
+    IndexedDict::<Envelope<EventRecord>>::require(DICT_EVENT_LOG);
+}
+
+/// Index a message and return its sequence number
+pub fn append(topic: &str, payload: &MessagePayload) -> u64 {
+    let payload_hash = match payload {
+        MessagePayload::Bytes(bytes) => hash::blake2b(bytes),
+        MessagePayload::String(text) => hash::blake2b(text),
+    };
+    log().push(Envelope(EventRecord {
+        topic: String::from(topic),
+        payload_hash,
+        block_height: runtime::get_block_height(),
+    }))
+}
@@ -0,0 +1,19 @@
+//! Publishing events
+//!
+//! Topics and payload structs live in the shared crate so off-chain
+//! indexers decode with the same definitions the contract encodes with.
+
+use casper_contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
+
+pub use casper_accelerate_shared::events::*;
+
+use crate::{error::Error, event_log};
+
+/// Publish an event under its topic and index it in `event_log`
+pub fn emit<E: Event>(event: &E) {
+    let payload = event
+        .to_payload()
+        .unwrap_or_revert_with(Error::Serialization);
+    event_log::append(E::TOPIC, &payload);
+    runtime::emit_message(E::TOPIC, &payload).unwrap_or_revert_with(Error::MessageEmitFailed);
+}
@@ -0,0 +1,178 @@
+//! Forced L2 withdrawals requested on L1
+//!
+//! A user who is being censored posts a withdrawal request here. Each batch
+//! declares the queue range `[from, to)` it processed, and `submit_batch`
+//! reverts while the oldest unprocessed request is older than the inclusion
+//! window, so the sequencer cannot make progress without honouring it.
+//!
+//! Every entry also extends a hash accumulator, `acc[i + 1] =
+//! blake2b(acc[i] || entry)`. `acc[from]` and `acc[to]` are public inputs
+//! of the batch proof, pinning exactly the requests it claims: a proof over
+//! a range that skips a request does not verify against the range the queue
+//! requires. The circuit binds the range but, lacking an in-circuit hash,
+//! does not yet check the batch's L2 withdrawals against the entries. The
+//! entry layout and `extend` live in the shared crate, so provers compute
+//! the same accumulators.
+//!
+//! Each L2 address may have one request waiting at a time, so the queue
+//! grows no faster than the number of registered addresses with a live
+//! request and cannot be flooded to stall batches.
+
+use alloc::string::String;
+use casper_accelerate_shared::envelope::Envelope;
+use casper_contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
+
+pub use casper_accelerate_shared::forced::{extend, ForcedTx};
+
+use crate::{
+    error::Error,
+    store::{self, IndexedDict, TypedDict},
+};
+
+const DICT_FORCED_QUEUE: &str = "forced_queue";
+const KEY_FORCED_HEAD: &str = "forced_head";
+const KEY_FORCED_WINDOW: &str = "forced_inclusion_window";
+const DICT_FORCED_ACC: &str = "forced_acc";
+const DICT_FORCED_LATEST: &str = "forced_latest";
+
+fn queue() -> IndexedDict<Envelope<ForcedTx>> {
+    IndexedDict::open(DICT_FORCED_QUEUE)
+}
+
+fn accumulators() -> TypedDict<u64, [u8; 32]> {
+    TypedDict::open(DICT_FORCED_ACC)
+}
+
+/// Queue index of each L2 address's most recent request
+fn latest() -> TypedDict<String, u64> {
+    TypedDict::open(DICT_FORCED_LATEST)
+}
+
+/// Accumulator after the first `index` entries; `acc[0]` is all zeroes
+pub fn accumulator(index: u64) -> [u8; 32] {
+    if index == 0 {
+        return [0u8; 32];
+    }
+    accumulators()
+        .get(&index)
+        .unwrap_or_revert_with(Error::MissingValue)
+}
+
+/// Create the queue; requests must be processed within `window` batches
+pub fn init(window: u64) {
+    IndexedDict::<Envelope<ForcedTx>>::create(DICT_FORCED_QUEUE);
+    TypedDict::<u64, [u8; 32]>::create(DICT_FORCED_ACC);
+    TypedDict::<String, u64>::create(DICT_FORCED_LATEST);
+    store::new_key(KEY_FORCED_HEAD, 0u64);
+    store::new_key(KEY_FORCED_WINDOW, window);
+}
+
+/// Verify queue storage after install
+pub fn self_check() {
+    IndexedDict::<Envelope<ForcedTx>>::require(DICT_FORCED_QUEUE);
+    store::require_key(DICT_FORCED_ACC);
+    store::require_key(DICT_FORCED_LATEST);
+    store::require_key(KEY_FORCED_HEAD);
+    store::require_key(KEY_FORCED_WINDOW);
+    let window: u64 = store::read_key(KEY_FORCED_WINDOW);
+    if head() > queue().len() || window == 0 {
+        runtime::revert(Error::SelfCheckFailed);
+    }
+}
+
+/// Queue a request and return its index
+///
+/// Reverts if the same L2 address has a request no batch has processed yet.
+pub fn enqueue(tx: ForcedTx) -> u64 {
+    let latest = latest();
+    if let Some(previous) = latest.get(&tx.l2_address) {
+        if previous >= head() {
+            runtime::revert(Error::ForcedWithdrawalPending);
+        }
+    }
+
+    let entry = Envelope(tx);
+    let index = queue().push(entry.clone());
+    latest.set(&entry.0.l2_address, index);
+
+    let acc = extend(accumulator(index), &entry).unwrap_or_revert_with(Error::Serialization);
+    accumulators().set(&(index + 1), acc);
+    index
+}
+
+/// Index of the oldest request not yet processed by a batch
+pub fn head() -> u64 {
+    store::read_key(KEY_FORCED_HEAD)
+}
+
+/// Check the batch numbered `batch` may process `[from, to)` of a queue of
+/// `len` requests starting at `head`
+///
+/// `remaining` is when request `to`, the oldest one the batch leaves, was
+/// queued, if there is one. A batch that stops short of a request older than
+/// `window` batches is rejected.
+pub fn check_range(
+    from: u64,
+    to: u64,
+    head: u64,
+    len: u64,
+    remaining: Option<u64>,
+    batch: u64,
+    window: u64,
+) -> Result<(), Error> {
+    if from != head || to < from || to > len {
+        return Err(Error::InvalidForcedRange);
+    }
+    match remaining {
+        Some(queued_at) if batch >= queued_at.saturating_add(window) => {
+            Err(Error::ForcedInclusionOverdue)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Mark `[from, to)` as processed by the batch numbered `batch` and return
+/// `(acc[from], acc[to])` for its public inputs
+///
+/// Reverts as `check_range` rejects the range.
+pub fn consume(from: u64, to: u64, batch: u64) -> ([u8; 32], [u8; 32]) {
+    let queue = queue();
+    let remaining = queue.get(to).map(|Envelope(tx)| tx.queued_at_batch);
+    let window: u64 = store::read_key(KEY_FORCED_WINDOW);
+    check_range(from, to, head(), queue.len(), remaining, batch, window)
+        .unwrap_or_else(|e| runtime::revert(e));
+    store::write_key(KEY_FORCED_HEAD, to);
+    (accumulator(from), accumulator(to))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_batch_that_skips_an_overdue_request_is_rejected() {
+        // Three requests from batch 4 on, a window of 2 and batch 6 to come:
+        // request 1 is overdue, so the batch must get past it
+        let check = |to, remaining| check_range(0, to, 0, 3, remaining, 6, 2);
+        assert_eq!(check(1, Some(4)), Err(Error::ForcedInclusionOverdue));
+        assert_eq!(check(0, Some(4)), Err(Error::ForcedInclusionOverdue));
+        assert_eq!(check(2, Some(5)), Ok(()));
+        assert_eq!(check(3, None), Ok(()));
+    }
+
+    #[test]
+    fn the_range_must_start_at_the_head_and_stay_in_the_queue() {
+        assert_eq!(
+            check_range(1, 2, 0, 3, None, 0, 2),
+            Err(Error::InvalidForcedRange)
+        );
+        assert_eq!(
+            check_range(2, 1, 2, 3, None, 0, 2),
+            Err(Error::InvalidForcedRange)
+        );
+        assert_eq!(
+            check_range(0, 4, 0, 3, None, 0, 2),
+            Err(Error::InvalidForcedRange)
+        );
+    }
+}
@@ -1,6 +1,7 @@
 #![no_std]
 #![no_main]
 #![feature(core_intrinsics)]
+#![allow(internal_features)]
 
 extern crate alloc;
 
@@ -14,14 +15,14 @@ fn panic(_info: &core::panic::PanicInfo) -> ! {
     core::intrinsics::abort()
 }
 
+#[allow(dead_code)]
+mod abi;
+
 use casper_contract::contract_api::{runtime, storage};
-use casper_contract::unwrap_or_revert::UnwrapOrRevert;
-use alloc::vec;
 use alloc::string::String;
-use casper_types::{
-    CLType, EntryPoint, EntryPointAccess, EntryPointType, EntryPoints,
-    Parameter, URef, U512,
-};
+use casper_types::{URef, U512};
+
+use abi::{arg, Access, ArgType, EntryPointDef};
 
 // Contract constants
 const CONTRACT_HASH_NAME: &str = "casper_accelerate_contract_hash";
@@ -57,46 +58,43 @@ pub extern "C" fn deposit() {
     // Stub: will be implemented after deployment
 }
 
-/// Contract installation entry point
-#[no_mangle]
-pub extern "C" fn call() {
-    let mut entry_points = EntryPoints::new();
-
-    entry_points.add_entry_point(EntryPoint::new(
-        ENTRY_POINT_INIT,
-        vec![],
-        CLType::Unit,
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
-
-    entry_points.add_entry_point(EntryPoint::new(
-        ENTRY_POINT_SUBMIT_BATCH,
-        vec![
-            Parameter::new(ARG_NEW_ROOT, CLType::U512),
-            Parameter::new(ARG_PROOF, CLType::U512),
+/// Entry point declarations
+const ENTRY_POINTS: &[EntryPointDef] = &[
+    EntryPointDef {
+        name: ENTRY_POINT_INIT,
+        args: &[],
+        ret: ArgType::Unit,
+        access: Access::Public,
+    },
+    EntryPointDef {
+        name: ENTRY_POINT_SUBMIT_BATCH,
+        args: &[
+            arg(ARG_NEW_ROOT, ArgType::U512),
+            arg(ARG_PROOF, ArgType::U512),
         ],
-        CLType::Unit,
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
-
-    entry_points.add_entry_point(EntryPoint::new(
-        ENTRY_POINT_DEPOSIT,
-        vec![
-            Parameter::new(ARG_AMOUNT, CLType::U512),
-            Parameter::new(ARG_PURSE, CLType::URef),
+        ret: ArgType::Unit,
+        access: Access::Public,
+    },
+    EntryPointDef {
+        name: ENTRY_POINT_DEPOSIT,
+        args: &[
+            arg(ARG_AMOUNT, ArgType::U512),
+            arg(ARG_PURSE, ArgType::URef),
         ],
-        CLType::Unit,
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
+        ret: ArgType::Unit,
+        access: Access::Public,
+    },
+];
 
+/// Contract installation entry point
+#[no_mangle]
+pub extern "C" fn call() {
     let (contract_hash, _version) = storage::new_contract(
-        entry_points,
+        abi::entry_points(ENTRY_POINTS),
         None,
         Some(String::from(CONTRACT_HASH_NAME)),
         Some(String::from(CONTRACT_PACKAGE_HASH_NAME)),
+        None, // message_topics - new in SDK v5
     );
 
     runtime::put_key(CONTRACT_HASH_NAME, contract_hash.into());
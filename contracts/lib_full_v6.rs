@@ -1,9 +1,37 @@
 #![no_std]
 #![no_main]
 #![feature(core_intrinsics)]
+#![allow(internal_features)]
 
 extern crate alloc;
 
+pub mod abi;
+pub mod args;
+pub mod assignment;
+pub mod audit;
+pub mod batches;
+pub mod bounties;
+pub mod caller;
+pub mod canary;
+pub mod deposit_address;
+pub mod endpoint;
+pub mod entry_points;
+pub mod error;
+pub mod event_log;
+pub mod events;
+pub mod forced;
+pub mod network;
+pub mod public_inputs;
+pub mod roles;
+pub mod screening;
+pub mod stats;
+pub mod store;
+pub use casper_accelerate_shared::{ct, envelope, hash, merkle, proof, types};
+pub mod verifier;
+pub mod wcspr_entry_points;
+pub mod withdrawals;
+pub mod wrapped;
+
 #[cfg(not(test))]
 #[global_allocator]
 static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
@@ -14,65 +42,201 @@ fn panic(_info: &core::panic::PanicInfo) -> ! {
     core::intrinsics::abort()
 }
 
-use casper_contract::contract_api::{runtime, storage, system};
+use casper_contract::contract_api::{cryptography, runtime, storage, system};
 use casper_contract::unwrap_or_revert::UnwrapOrRevert;
-use alloc::vec;
+use alloc::collections::BTreeMap;
 use alloc::string::String;
 use casper_types::{
-    CLType, U512, Parameter, URef,
-    addressable_entity::{
-        EntryPointAccess, EntryPointType, EntryPoints, EntityEntryPoint, EntryPointPayment,
-    },
+    account::AccountHash,
+    bytesrepr::{Bytes, ToBytes},
+    bytesrepr::FromBytes,
+    contracts::ContractPackageHash,
+    contract_messages::MessageTopicOperation,
+    runtime_args, CLValue, Key, PublicKey, RuntimeArgs, Signature, U512, URef,
 };
 
-// Contract constants
-const CONTRACT_HASH_NAME: &str = "casper_accelerate_hash";
-const CONTRACT_PACKAGE_NAME: &str = "casper_accelerate_package";
-
-// Entry point names
-const EP_INIT: &str = "init";
-const EP_SUBMIT_BATCH: &str = "submit_batch";
-const EP_DEPOSIT: &str = "deposit";
-const EP_WITHDRAW: &str = "withdraw";
-const EP_GET_STATE: &str = "get_state";
-
-// Argument names
-const ARG_ROOT: &str = "root";
-const ARG_PROOF: &str = "proof";
-const ARG_AMOUNT: &str = "amount";
-const ARG_PURSE: &str = "purse";
-const ARG_L2_ADDRESS: &str = "l2_address";
-const ARG_RECIPIENT: &str = "recipient";
+use args::RawArg;
+use batches::BatchRecord;
+use error::Error;
+use endpoint::EndpointMetadata;
+use entry_points::*;
+use events::{
+    BatchSubmitted, BountyClaimed, BountyPosted, ConfigChanged, Deposit,
+    ForcedWithdrawalRequested, KeyedDeposit, Paused, RootDisputed, Withdrawal,
+    WithdrawalCancelled, WithdrawalExpired, WithdrawalReactivated, WithdrawalAssigned,
+    WithdrawalWrapped,
+};
+use forced::ForcedTx;
+use proof::{BatchProof, MerkleProof};
+use public_inputs::PublicInputs;
+use network::{
+    CHAIN_NAME, CONTRACT_ABI_NAME, CONTRACT_HASH_NAME, CONTRACT_PACKAGE_NAME,
+    FORCED_INCLUSION_WINDOW, WITHDRAWAL_CHALLENGE_WINDOW, WITHDRAWAL_GRACE_WINDOW,
+};
+use store::TypedDict;
+use types::{AuditAction, ProofSystem, ScreeningMode};
 
 // Storage keys
 const KEY_STATE_ROOT: &str = "state_root";
 const KEY_BATCH_COUNT: &str = "batch_count";
 const KEY_TOTAL_DEPOSITS: &str = "total_deposits";
+const KEY_DEPOSIT_COUNT: &str = "deposit_count";
 const KEY_TOTAL_WITHDRAWALS: &str = "total_withdrawals";
 const KEY_CONTRACT_PURSE: &str = "contract_purse";
+const KEY_INSURANCE_PURSE: &str = "insurance_purse";
+const KEY_PAUSED: &str = "paused";
+const KEY_CONTRACT_PACKAGE: &str = "contract_package_hash";
+const DICT_L2_KEYS: &str = "l2_keys";
+const DICT_L2_OWNERS: &str = "l2_owners";
+const DICT_CLAIMED_WITHDRAWALS: &str = "claimed_withdrawals";
 
 /// Initialize contract - stores initial state root and creates contract purse
+/// Arguments: verifying_key (Bytes, compressed BN254 Groth16 key),
+/// circuit_id (32 bytes, published with the key),
+/// contract_package_hash (Key, this contract's package, passed by `call`)
 #[no_mangle]
 pub extern "C" fn init() {
+    if runtime::has_key(KEY_STATE_ROOT) {
+        runtime::revert(Error::AlreadyInitialized);
+    }
+
+    // Key every batch proof is checked against
+    let verifying_key: Bytes = runtime::get_named_arg(ARG_VERIFYING_KEY);
+    let circuit_id: [u8; 32] = runtime::get_named_arg(ARG_CIRCUIT_ID);
+    verifier::init(verifying_key, circuit_id);
+
+    // Optional canary limits for pilot deployments
+    let tvl_cap = RawArg::try_get(ARG_CANARY_TVL_CAP).map(|arg| arg.parse::<U512>());
+    let expiry_height = RawArg::try_get(ARG_CANARY_EXPIRY_HEIGHT).map(|arg| arg.parse::<u64>());
+    canary::init(tvl_cap, expiry_height);
+
+    // Own package hash, which signed messages such as assignments bind to
+    let package: Key = runtime::get_named_arg(ARG_CONTRACT_PACKAGE_HASH);
+    let package = package
+        .into_hash_addr()
+        .unwrap_or_revert_with(Error::InvalidArgument);
+    store::new_key(KEY_CONTRACT_PACKAGE, package);
+
     // Initialize state root to 0
-    let root_uref = storage::new_uref(U512::zero());
-    runtime::put_key(KEY_STATE_ROOT, root_uref.into());
+    store::new_key(KEY_STATE_ROOT, U512::zero());
 
     // Initialize batch counter
-    let count_uref = storage::new_uref(0u64);
-    runtime::put_key(KEY_BATCH_COUNT, count_uref.into());
+    store::new_key(KEY_BATCH_COUNT, 0u64);
 
     // Initialize deposit counter
-    let deposits_uref = storage::new_uref(U512::zero());
-    runtime::put_key(KEY_TOTAL_DEPOSITS, deposits_uref.into());
+    store::new_key(KEY_TOTAL_DEPOSITS, U512::zero());
+
+    // Sequence number of the next deposit event
+    store::new_key(KEY_DEPOSIT_COUNT, 0u64);
 
     // Initialize withdrawal counter
-    let withdrawals_uref = storage::new_uref(U512::zero());
-    runtime::put_key(KEY_TOTAL_WITHDRAWALS, withdrawals_uref.into());
+    store::new_key(KEY_TOTAL_WITHDRAWALS, U512::zero());
 
     // Create contract purse for holding deposited funds
     let contract_purse = system::create_purse();
     runtime::put_key(KEY_CONTRACT_PURSE, contract_purse.into());
+
+    // Insurance fund, holding the funds of expired withdrawals
+    let insurance_purse = system::create_purse();
+    runtime::put_key(KEY_INSURANCE_PURSE, insurance_purse.into());
+
+    // Append-only log of privileged calls
+    audit::init();
+    event_log::init();
+
+    // Address screening starts disabled, managed by the initializing account
+    screening::init(runtime::get_caller());
+
+    // The installer starts as both admin and sequencer
+    roles::init(runtime::get_caller(), runtime::get_caller());
+
+    // Forced withdrawal queue for censorship resistance
+    forced::init(FORCED_INCLUSION_WINDOW);
+
+    // Public-input digest and metadata of each accepted batch
+    public_inputs::init();
+    batches::init();
+
+    // Exchange-style deposit addresses
+    deposit_address::init();
+    stats::init();
+
+    // Bridge starts unpaused
+    store::new_key(KEY_PAUSED, false);
+
+    // L2 public keys registered alongside deposits, by L2 address
+    TypedDict::<String, Bytes>::create(DICT_L2_KEYS);
+
+    // L1 account that registered each L2 address, the only one that may
+    // force its withdrawal
+    TypedDict::<String, AccountHash>::create(DICT_L2_OWNERS);
+
+    // Withdrawal leaves already proven, so a proof can only be used once
+    TypedDict::<[u8; 32], bool>::create(DICT_CLAIMED_WITHDRAWALS);
+
+    // Proven withdrawals waiting out the challenge window
+    withdrawals::init(WITHDRAWAL_CHALLENGE_WINDOW, WITHDRAWAL_GRACE_WINDOW);
+
+    // wCSPR redemptions; the token is configured after it is installed
+    wrapped::init();
+
+    // Fee purse escrowing proving bounties
+    bounties::init();
+}
+
+/// Verify every named key, dictionary and the purse exist and agree
+/// Run at the end of installation so a partial install reverts atomically
+#[no_mangle]
+pub extern "C" fn self_check() {
+    for key in [
+        KEY_STATE_ROOT,
+        KEY_BATCH_COUNT,
+        KEY_TOTAL_DEPOSITS,
+        KEY_DEPOSIT_COUNT,
+        KEY_TOTAL_WITHDRAWALS,
+        KEY_CONTRACT_PURSE,
+        KEY_INSURANCE_PURSE,
+        KEY_PAUSED,
+        KEY_CONTRACT_PACKAGE,
+        DICT_L2_KEYS,
+        DICT_L2_OWNERS,
+        DICT_CLAIMED_WITHDRAWALS,
+    ] {
+        store::require_key(key);
+    }
+
+    // Typed reads revert if a value is missing or malformed
+    let _root: U512 = store::read_key(KEY_STATE_ROOT);
+    let _deposits: U512 = store::read_key(KEY_TOTAL_DEPOSITS);
+    let _withdrawals: U512 = store::read_key(KEY_TOTAL_WITHDRAWALS);
+    system::get_purse_balance(store::key_uref(KEY_CONTRACT_PURSE))
+        .unwrap_or_revert_with(Error::SelfCheckFailed);
+    system::get_purse_balance(store::key_uref(KEY_INSURANCE_PURSE))
+        .unwrap_or_revert_with(Error::SelfCheckFailed);
+
+    // Per-batch records must line up with the batch counter
+    let batch_count: u64 = store::read_key(KEY_BATCH_COUNT);
+    if public_inputs::len() != batch_count
+        || batches::len() != batch_count
+        || stats::proof_count() != batch_count
+    {
+        runtime::revert(Error::SelfCheckFailed);
+    }
+
+    audit::self_check();
+    batches::self_check();
+    bounties::self_check();
+    canary::self_check();
+    event_log::self_check();
+    screening::self_check();
+    roles::self_check();
+    forced::self_check();
+    public_inputs::self_check();
+    deposit_address::self_check();
+    stats::self_check();
+    verifier::self_check();
+    withdrawals::self_check();
+    wrapped::self_check();
 }
 
 /// Deposit CSPR into the L2 rollup
@@ -80,6 +244,10 @@ pub extern "C" fn init() {
 /// Arguments: amount (U512), purse (URef), l2_address (String)
 #[no_mangle]
 pub extern "C" fn deposit() {
+    stats::hit(EP_DEPOSIT);
+    require_not_paused();
+    screening::check(runtime::get_caller());
+
     // Get deposit amount
     let amount: U512 = runtime::get_named_arg(ARG_AMOUNT);
 
@@ -87,81 +255,691 @@ pub extern "C" fn deposit() {
     let source_purse: URef = runtime::get_named_arg(ARG_PURSE);
 
     // Get L2 address to credit
-    let _l2_address: String = runtime::get_named_arg(ARG_L2_ADDRESS);
+    let l2_address: String = runtime::get_named_arg(ARG_L2_ADDRESS);
 
     // Get contract purse
-    let purse_key = runtime::get_key(KEY_CONTRACT_PURSE).unwrap_or_revert();
-    let contract_purse: URef = purse_key.into_uref().unwrap_or_revert();
+    let contract_purse = store::key_uref(KEY_CONTRACT_PURSE);
 
     // Transfer from caller's purse to contract purse
     system::transfer_from_purse_to_purse(source_purse, contract_purse, amount, None)
-        .unwrap_or_revert();
+        .unwrap_or_revert_with(Error::DepositTransferFailed);
+
+    record_deposit(runtime::get_caller(), amount, l2_address);
+}
+
+/// Deposit and register the L2 account's public key in one call
+/// Arguments: amount (U512), purse (URef), l2_address (String), l2_public_key (Bytes)
+#[no_mangle]
+pub extern "C" fn deposit_with_key() {
+    stats::hit(EP_DEPOSIT_WITH_KEY);
+    require_not_paused();
+    let depositor = runtime::get_caller();
+    screening::check(depositor);
+
+    let amount: U512 = runtime::get_named_arg(ARG_AMOUNT);
+    let source_purse: URef = runtime::get_named_arg(ARG_PURSE);
+    let l2_address: String = runtime::get_named_arg(ARG_L2_ADDRESS);
+    let l2_public_key: Bytes = runtime::get_named_arg(ARG_L2_PUBLIC_KEY);
+
+    // First registration wins; re-registering the same key is a no-op
+    let l2_keys = TypedDict::<String, Bytes>::open(DICT_L2_KEYS);
+    match l2_keys.get(&l2_address) {
+        Some(existing) if !ct::eq(&existing, &l2_public_key) => {
+            runtime::revert(Error::L2KeyMismatch)
+        }
+        Some(_) => {}
+        None => {
+            l2_keys.set(&l2_address, l2_public_key.clone());
+            TypedDict::<String, AccountHash>::open(DICT_L2_OWNERS).set(&l2_address, depositor);
+        }
+    }
+
+    let contract_purse = store::key_uref(KEY_CONTRACT_PURSE);
+    system::transfer_from_purse_to_purse(source_purse, contract_purse, amount, None)
+        .unwrap_or_revert_with(Error::DepositTransferFailed);
+
+    record_deposit(depositor, amount, l2_address.clone());
+    events::emit(&KeyedDeposit {
+        depositor,
+        amount,
+        l2_address,
+        l2_public_key,
+    });
+}
+
+/// Book a deposit by `depositor` that has already reached the contract purse
+fn record_deposit(depositor: AccountHash, amount: U512, l2_address: String) {
+    // Canary installs cap the value held and stop deposits at expiry
+    let contract_purse = store::key_uref(KEY_CONTRACT_PURSE);
+    let tvl = system::get_purse_balance(contract_purse).unwrap_or_revert_with(Error::InvalidPurse);
+    canary::check_deposit(tvl);
 
     // Update total deposits
-    let deposits_key = runtime::get_key(KEY_TOTAL_DEPOSITS).unwrap_or_revert();
-    let deposits_uref = deposits_key.into_uref().unwrap_or_revert();
-    let current_deposits: U512 = storage::read(deposits_uref)
-        .unwrap_or_revert()
-        .unwrap_or_revert();
-    storage::write(deposits_uref, current_deposits + amount);
+    let current_deposits: U512 = store::read_key(KEY_TOTAL_DEPOSITS);
+    store::write_key(KEY_TOTAL_DEPOSITS, current_deposits + amount);
 
-    // Note: In production, emit an event for the sequencer to pick up
-    // The sequencer will credit the L2 address with the deposited amount
+    // The sequencer subscribes to this topic and credits the L2 address
+    let deposit_index: u64 = store::read_key(KEY_DEPOSIT_COUNT);
+    store::write_key(KEY_DEPOSIT_COUNT, deposit_index + 1);
+    events::emit(&Deposit {
+        depositor,
+        amount,
+        l2_address,
+        deposit_index,
+    });
 }
 
-/// Withdraw CSPR from L2 back to L1
-/// Requires a valid Merkle proof of the withdrawal
+/// Register the caller's deterministic deposit address for an L2 account
+/// Returns an add-only URef to the address purse for third parties to fund
+#[no_mangle]
+pub extern "C" fn register_deposit_address() {
+    stats::hit(EP_REGISTER_DEPOSIT_ADDRESS);
+    require_not_paused();
+    // No new deposit addresses once a canary install stops taking deposits
+    canary::require_active();
+    let owner = runtime::get_caller();
+    screening::check(owner);
+    let l2_address: String = runtime::get_named_arg(ARG_L2_ADDRESS);
+    let salt: u64 = runtime::get_named_arg(ARG_SALT);
+    let (_id, purse) = deposit_address::register(owner, l2_address, salt);
+    runtime::ret(CLValue::from_t(purse).unwrap_or_revert_with(Error::Serialization));
+}
+
+/// Move funds received at a deposit address into the bridge and credit them
+/// Anyone may sweep; the deposit is screened and booked as the address owner's
+#[no_mangle]
+pub extern "C" fn sweep_deposit() {
+    stats::hit(EP_SWEEP_DEPOSIT);
+    require_not_paused();
+
+    let deposit_id: [u8; 32] = runtime::get_named_arg(ARG_DEPOSIT_ID);
+    let contract_purse = store::key_uref(KEY_CONTRACT_PURSE);
+    let (owner, l2_address, amount) = deposit_address::sweep(&deposit_id, contract_purse);
+    // Re-screened in case the owner was listed after registering
+    screening::check(owner);
+
+    record_deposit(owner, amount, l2_address);
+}
+
+/// Prove a withdrawal from L2 back to L1 and queue it for payout
+/// Requires a valid Merkle proof of the withdrawal; returns the withdrawal
+/// index to pass to `finalize_withdrawal` once the challenge window passes
 #[no_mangle]
 pub extern "C" fn withdraw() {
+    stats::hit(EP_WITHDRAW);
+    require_not_paused();
+    screening::check(runtime::get_caller());
+
     // Get withdrawal amount
     let amount: U512 = runtime::get_named_arg(ARG_AMOUNT);
 
-    // Get proof (Merkle inclusion proof for withdrawal)
-    let _proof: U512 = runtime::get_named_arg(ARG_PROOF);
-
     // Get recipient purse
     let recipient: URef = runtime::get_named_arg(ARG_RECIPIENT);
 
-    // TODO: Verify Merkle proof that this withdrawal is valid
-    // For now, we trust the caller (in production, verify against state root)
+    let recipient = Key::URef(recipient);
+    queue_withdrawal(recipient, amount);
+}
 
-    // Get contract purse
-    let purse_key = runtime::get_key(KEY_CONTRACT_PURSE).unwrap_or_revert();
-    let contract_purse: URef = purse_key.into_uref().unwrap_or_revert();
+/// Queue a withdrawal paid straight to an account, creating it if needed
+/// For first-time Casper users who have no purse to grant rights on
+#[no_mangle]
+pub extern "C" fn withdraw_to_account() {
+    stats::hit(EP_WITHDRAW_TO_ACCOUNT);
+    require_not_paused();
+    screening::check(runtime::get_caller());
+
+    let amount: U512 = runtime::get_named_arg(ARG_AMOUNT);
+    let recipient: AccountHash = runtime::get_named_arg(ARG_RECIPIENT);
+
+    let recipient = Key::Account(recipient);
+    queue_withdrawal(recipient, amount);
+}
+
+/// Pay out a queued withdrawal once its challenge window has passed
+/// Arguments: withdrawal_index (u64, as returned by `withdraw`)
+#[no_mangle]
+pub extern "C" fn finalize_withdrawal() {
+    stats::hit(EP_FINALIZE_WITHDRAWAL);
+    require_not_paused();
+
+    let index: u64 = runtime::get_named_arg(ARG_WITHDRAWAL_INDEX);
+    let withdrawal = withdrawals::finalize(index);
+    release(withdrawal.amount, withdrawal.recipient);
+}
+
+/// Pay a pending withdrawal to another purse or account, on the strength of
+/// the recipient's signature over the assignment; returns its amount
+/// Arguments: withdrawal_index (u64), recipient (Key, the new recipient),
+/// public_key (PublicKey of the current recipient account), signature
+/// (Bytes, serialized `Signature` over `assignment::digest`)
+#[no_mangle]
+pub extern "C" fn claim_to() {
+    stats::hit(EP_CLAIM_TO);
+    require_not_paused();
+
+    let index: u64 = runtime::get_named_arg(ARG_WITHDRAWAL_INDEX);
+    let new_recipient: Key = runtime::get_named_arg(ARG_RECIPIENT);
+    if !matches!(new_recipient, Key::Account(_) | Key::URef(_)) {
+        runtime::revert(Error::InvalidArgument);
+    }
+    let public_key: PublicKey = runtime::get_named_arg(ARG_PUBLIC_KEY);
+    let signature_arg = RawArg::get(ARG_SIGNATURE);
+    let (signature, rem) = Signature::from_bytes(signature_arg.as_bytes())
+        .unwrap_or_revert_with(Error::InvalidAssignment);
+    if !rem.is_empty() {
+        runtime::revert(Error::InvalidAssignment);
+    }
+    let package: [u8; 32] = store::read_key(KEY_CONTRACT_PACKAGE);
+    let digest = assignment::digest(CHAIN_NAME, package, index, &new_recipient)
+        .unwrap_or_revert_with(Error::Serialization);
+    cryptography::verify_signature(digest, &signature, &public_key)
+        .unwrap_or_revert_with(Error::InvalidAssignment);
+
+    let from = public_key.to_account_hash();
+    let withdrawal = withdrawals::assign(index, from, new_recipient);
+    events::emit(&WithdrawalAssigned {
+        index,
+        from,
+        to: new_recipient,
+        amount: withdrawal.amount,
+    });
+    runtime::ret(CLValue::from_t(withdrawal.amount).unwrap_or_revert_with(Error::Serialization));
+}
+
+/// Mint wCSPR to the caller against its pending withdrawal, so it need not
+/// wait out the challenge window
+/// Arguments: withdrawal_index (u64)
+#[no_mangle]
+pub extern "C" fn wrap_withdrawal() {
+    stats::hit(EP_WRAP_WITHDRAWAL);
+    require_not_paused();
+
+    let index: u64 = runtime::get_named_arg(ARG_WITHDRAWAL_INDEX);
+    let owner = caller::immediate();
+    let withdrawal = withdrawals::wrap(index, owner);
+    wrapped::mint(index, owner, withdrawal.amount);
+    events::emit(&WithdrawalWrapped {
+        index,
+        owner,
+        amount: withdrawal.amount,
+    });
+}
+
+/// Burn the caller's wCSPR and pay the same amount of CSPR out of a wrapped
+/// withdrawal whose challenge window has passed on an undisputed root
+/// Arguments: withdrawal_index (u64), amount (U512), purse (optional URef,
+/// required when the caller is a contract; otherwise the caller's account
+/// is paid)
+#[no_mangle]
+pub extern "C" fn redeem_wcspr() {
+    stats::hit(EP_REDEEM_WCSPR);
+    require_not_paused();
+
+    let index: u64 = runtime::get_named_arg(ARG_WITHDRAWAL_INDEX);
+    let amount: U512 = runtime::get_named_arg(ARG_AMOUNT);
+    if amount.is_zero() {
+        runtime::revert(Error::InvalidAmount);
+    }
+    let holder = caller::immediate();
+    let recipient = match RawArg::try_get(ARG_PURSE) {
+        Some(purse) => Key::URef(purse.parse::<URef>()),
+        None if matches!(holder, Key::Account(_)) => holder,
+        None => runtime::revert(Error::MissingPurse),
+    };
+
+    withdrawals::redeemable(index);
+    if wrapped::draw(index, amount).is_zero() {
+        withdrawals::settle_wrapped(index);
+    }
+    wrapped::burn(holder, amount);
+    release(amount, recipient);
+}
+
+/// Point the bridge at the installed wCSPR token package
+/// Arguments: token (Key, the token's contract package hash)
+#[no_mangle]
+pub extern "C" fn set_wcspr_token() {
+    stats::hit(EP_SET_WCSPR_TOKEN);
+    let admin = roles::require_admin();
+    let token: Key = runtime::get_named_arg(ARG_TOKEN);
+    let package = token
+        .into_hash_addr()
+        .map(ContractPackageHash::new)
+        .unwrap_or_revert_with(Error::InvalidArgument);
+    wrapped::set_token(package);
+    let args = token.to_bytes().unwrap_or_revert_with(Error::Serialization);
+    audit::record(AuditAction::SetWcsprToken, &args);
+    events::emit(&ConfigChanged {
+        key: String::from(wrapped::KEY_WCSPR_TOKEN),
+        by: admin,
+    });
+}
+
+/// Rotate the batch verifying key
+/// The key is checked against its published circuit identifier before it
+/// replaces the current one
+/// Arguments: verifying_key (Bytes), circuit_id (32 bytes)
+#[no_mangle]
+pub extern "C" fn set_verification_key() {
+    stats::hit(EP_SET_VERIFICATION_KEY);
+    let admin = roles::require_admin();
+    let verifying_key: Bytes = runtime::get_named_arg(ARG_VERIFYING_KEY);
+    let circuit_id: [u8; 32] = runtime::get_named_arg(ARG_CIRCUIT_ID);
+    let args = (circuit_id, verifying_key.clone())
+        .to_bytes()
+        .unwrap_or_revert_with(Error::Serialization);
+    verifier::set(verifying_key, circuit_id);
+    audit::record(AuditAction::SetVerificationKey, &args);
+    events::emit(&ConfigChanged {
+        key: String::from(verifier::KEY_CIRCUIT_ID),
+        by: admin,
+    });
+}
+
+/// Accept or stop accepting new batches proven with a proof system
+/// Accepting a second system opens a migration's transition window; turning
+/// the old one off closes it
+/// Arguments: proof_system (u8), accepted (bool)
+#[no_mangle]
+pub extern "C" fn set_proof_system() {
+    stats::hit(EP_SET_PROOF_SYSTEM);
+    let admin = roles::require_admin();
+    let proof_system: ProofSystem = runtime::get_named_arg(ARG_PROOF_SYSTEM);
+    let accepted: bool = runtime::get_named_arg(ARG_ACCEPTED);
+    verifier::set_accepted(proof_system, accepted);
+    let args = (proof_system, accepted)
+        .to_bytes()
+        .unwrap_or_revert_with(Error::Serialization);
+    audit::record(AuditAction::SetProofSystem, &args);
+    events::emit(&ConfigChanged {
+        key: String::from(verifier::DICT_PROOF_SYSTEMS),
+        by: admin,
+    });
+}
+
+/// Flag a state root as bad, blocking payout of every withdrawal proven
+/// against it
+/// Arguments: root (U512)
+#[no_mangle]
+pub extern "C" fn dispute_root() {
+    stats::hit(EP_DISPUTE_ROOT);
+    let admin = roles::require_admin();
+
+    let root: U512 = runtime::get_named_arg(ARG_ROOT);
+    withdrawals::dispute_root(root);
+    let args = root.to_bytes().unwrap_or_revert_with(Error::Serialization);
+    audit::record(AuditAction::DisputeRoot, &args);
+    events::emit(&RootDisputed { root, by: admin });
+}
+
+/// Drop a pending withdrawal proven against a disputed root and unmark its
+/// leaf, so it can be proven again against a later root
+/// Arguments: withdrawal_index (u64)
+#[no_mangle]
+pub extern "C" fn cancel_withdrawal() {
+    stats::hit(EP_CANCEL_WITHDRAWAL);
+    require_not_paused();
+
+    let index: u64 = runtime::get_named_arg(ARG_WITHDRAWAL_INDEX);
+    let withdrawal = withdrawals::cancel(index);
+    TypedDict::<[u8; 32], bool>::open(DICT_CLAIMED_WITHDRAWALS).remove(&withdrawal.leaf);
+    events::emit(&WithdrawalCancelled {
+        index,
+        recipient: withdrawal.recipient,
+        amount: withdrawal.amount,
+    });
+}
 
-    // Transfer from contract to recipient
-    system::transfer_from_purse_to_purse(contract_purse, recipient, amount, None)
-        .unwrap_or_revert();
+/// Move a withdrawal left unclaimed past the expiry window to the insurance
+/// fund
+/// Arguments: withdrawal_index (u64)
+#[no_mangle]
+pub extern "C" fn expire_withdrawal() {
+    stats::hit(EP_EXPIRE_WITHDRAWAL);
+    require_not_paused();
+
+    let index: u64 = runtime::get_named_arg(ARG_WITHDRAWAL_INDEX);
+    let withdrawal = withdrawals::expire(index);
+    system::transfer_from_purse_to_purse(
+        store::key_uref(KEY_CONTRACT_PURSE),
+        store::key_uref(KEY_INSURANCE_PURSE),
+        withdrawal.amount,
+        None,
+    )
+    .unwrap_or_revert_with(Error::WithdrawalTransferFailed);
+    events::emit(&WithdrawalExpired {
+        index,
+        recipient: withdrawal.recipient,
+        amount: withdrawal.amount,
+    });
+}
+
+/// Bring an expired withdrawal's funds back from the insurance fund within
+/// its grace window, so it can be finalized
+/// Arguments: withdrawal_index (u64)
+#[no_mangle]
+pub extern "C" fn reactivate_withdrawal() {
+    stats::hit(EP_REACTIVATE_WITHDRAWAL);
+    require_not_paused();
+
+    let index: u64 = runtime::get_named_arg(ARG_WITHDRAWAL_INDEX);
+    let withdrawal = withdrawals::reactivate(index);
+    system::transfer_from_purse_to_purse(
+        store::key_uref(KEY_INSURANCE_PURSE),
+        store::key_uref(KEY_CONTRACT_PURSE),
+        withdrawal.amount,
+        None,
+    )
+    .unwrap_or_revert_with(Error::WithdrawalTransferFailed);
+    events::emit(&WithdrawalReactivated {
+        index,
+        recipient: withdrawal.recipient,
+        amount: withdrawal.amount,
+    });
+}
+
+/// Set how long a finalizable withdrawal may sit unclaimed before it can be
+/// expired; 0 turns expiry off
+/// Arguments: expiry_window (u64, blocks)
+#[no_mangle]
+pub extern "C" fn set_withdrawal_expiry() {
+    stats::hit(EP_SET_WITHDRAWAL_EXPIRY);
+    let admin = roles::require_admin();
+    let window: u64 = runtime::get_named_arg(ARG_EXPIRY_WINDOW);
+    withdrawals::set_expiry_window(window);
+    let args = window.to_bytes().unwrap_or_revert_with(Error::Serialization);
+    audit::record(AuditAction::SetWithdrawalExpiry, &args);
+    events::emit(&ConfigChanged {
+        key: String::from(withdrawals::KEY_EXPIRY_WINDOW),
+        by: admin,
+    });
+}
+
+/// Verify the withdrawal proof, queue the withdrawal and return its index
+fn queue_withdrawal(recipient: Key, amount: U512) {
+    let (leaf, root) = verify_withdrawal(&recipient, amount);
+    let index = withdrawals::enqueue(recipient, amount, leaf, root);
+    runtime::ret(CLValue::from_t(index).unwrap_or_revert_with(Error::Serialization));
+}
+
+/// Revert unless `proof` shows the withdrawal leaf is in the current state root
+/// and the leaf has not been claimed before, then mark it claimed and return
+/// the leaf and the root it was proven against
+/// Arguments read here: nonce (u64), proof (Bytes of concatenated 32-byte siblings)
+fn verify_withdrawal(recipient: &Key, amount: U512) -> ([u8; 32], U512) {
+    let nonce: u64 = runtime::get_named_arg(ARG_NONCE);
+    let proof_arg = RawArg::get(ARG_PROOF);
+    let proof = MerkleProof::parse(proof_arg.as_bytes()).unwrap_or_else(|e| runtime::revert(e));
+
+    let leaf = merkle::withdrawal_leaf(recipient, amount, nonce)
+        .unwrap_or_revert_with(Error::Serialization);
+    let root: U512 = store::read_key(KEY_STATE_ROOT);
+    if !merkle::verify(leaf, &proof, root) {
+        runtime::revert(Error::InvalidMerkleProof);
+    }
+
+    let claimed = TypedDict::<[u8; 32], bool>::open(DICT_CLAIMED_WITHDRAWALS);
+    if claimed.contains(&leaf) {
+        runtime::revert(Error::WithdrawalAlreadyClaimed);
+    }
+    claimed.set(&leaf, true);
+    (leaf, root)
+}
+
+/// Pay `amount` out of `source` to a purse or account
+fn pay_out(source: URef, amount: U512, recipient: Key) {
+    match recipient {
+        Key::URef(purse) => {
+            system::transfer_from_purse_to_purse(source, purse, amount, None)
+                .unwrap_or_revert_with(Error::WithdrawalTransferFailed);
+        }
+        Key::Account(account) => {
+            system::transfer_from_purse_to_account(source, account, amount, None)
+                .unwrap_or_revert_with(Error::WithdrawalTransferFailed);
+        }
+        _ => runtime::revert(Error::InvalidArgument),
+    }
+}
+
+/// Pay `amount` out of the contract purse to a purse or account
+fn release(amount: U512, recipient: Key) {
+    pay_out(store::key_uref(KEY_CONTRACT_PURSE), amount, recipient);
 
     // Update total withdrawals
-    let withdrawals_key = runtime::get_key(KEY_TOTAL_WITHDRAWALS).unwrap_or_revert();
-    let withdrawals_uref = withdrawals_key.into_uref().unwrap_or_revert();
-    let current_withdrawals: U512 = storage::read(withdrawals_uref)
-        .unwrap_or_revert()
-        .unwrap_or_revert();
-    storage::write(withdrawals_uref, current_withdrawals + amount);
+    let current_withdrawals: U512 = store::read_key(KEY_TOTAL_WITHDRAWALS);
+    store::write_key(KEY_TOTAL_WITHDRAWALS, current_withdrawals + amount);
+
+    events::emit(&Withdrawal { recipient, amount });
+}
+
+/// Decode a batch proof for the proof system the caller declares, Groth16
+/// if it declares none, reverting unless that system is accepted
+/// Arguments read here: proof_system (optional u8)
+fn batch_proof(proof: &RawArg) -> (ProofSystem, BatchProof) {
+    let system = RawArg::try_get(ARG_PROOF_SYSTEM)
+        .map(|arg| arg.parse::<ProofSystem>())
+        .unwrap_or(ProofSystem::Groth16Bn254);
+    if !verifier::accepts(system) {
+        runtime::revert(Error::ProofSystemNotAccepted);
+    }
+    let proof = BatchProof::parse(system, proof.as_bytes()).unwrap_or_else(|e| runtime::revert(e));
+    (system, proof)
+}
+
+/// Escrow a reward from `purse` in the fee purse for whoever first proves
+/// the batch `batch_hash` (see `bounties::batch_hash`); posting again for the
+/// same batch adds to the reward
+/// Arguments: batch_hash (32 bytes), reward (U512), purse (URef)
+#[no_mangle]
+pub extern "C" fn post_proving_bounty() {
+    stats::hit(EP_POST_PROVING_BOUNTY);
+    require_not_paused();
+    roles::require_sequencer();
+
+    let batch_hash: [u8; 32] = runtime::get_named_arg(ARG_BATCH_HASH);
+    let reward: U512 = runtime::get_named_arg(ARG_REWARD);
+    let purse: URef = runtime::get_named_arg(ARG_PURSE);
+    if reward.is_zero() {
+        runtime::revert(Error::InvalidAmount);
+    }
+    system::transfer_from_purse_to_purse(purse, bounties::fee_purse(), reward, None)
+        .unwrap_or_revert_with(Error::DepositTransferFailed);
+    let total = bounties::post(batch_hash, reward);
+
+    let args = (batch_hash, reward)
+        .to_bytes()
+        .unwrap_or_revert_with(Error::Serialization);
+    audit::record(AuditAction::PostProvingBounty, &args);
+    events::emit(&BountyPosted {
+        batch_hash,
+        reward: total,
+    });
+}
+
+/// Commit to a later `claim_bounty` (see `bounties::claim_commitment`)
+/// Arguments: commitment (32 bytes)
+#[no_mangle]
+pub extern "C" fn commit_bounty_claim() {
+    stats::hit(EP_COMMIT_BOUNTY_CLAIM);
+    require_not_paused();
+
+    let commitment: [u8; 32] = runtime::get_named_arg(ARG_COMMITMENT);
+    bounties::commit(commitment, runtime::get_block_height());
+}
+
+/// Pay the bounty for the batch from `prev_root` to `root` over the forced
+/// queue range `[forced_from, forced_to)` to the immediate caller, if
+/// `proof` proves that batch and the caller committed to this claim in an
+/// earlier block; returns the reward. A contract caller names a `purse`.
+/// Arguments: prev_root (U512), root (U512), forced_from (u64),
+/// forced_to (u64), proof (Bytes), proof_system (optional u8),
+/// purse (optional URef)
+#[no_mangle]
+pub extern "C" fn claim_bounty() {
+    stats::hit(EP_CLAIM_BOUNTY);
+    require_not_paused();
+
+    let prover = caller::immediate();
+    let recipient = match RawArg::try_get(ARG_PURSE) {
+        Some(purse) => Key::URef(purse.parse::<URef>()),
+        None if matches!(prover, Key::Account(_)) => prover,
+        None => runtime::revert(Error::MissingPurse),
+    };
+
+    let prev_root: U512 = runtime::get_named_arg(ARG_PREV_ROOT);
+    let new_root: U512 = runtime::get_named_arg(ARG_ROOT);
+    let forced_from: u64 = runtime::get_named_arg(ARG_FORCED_FROM);
+    let forced_to: u64 = runtime::get_named_arg(ARG_FORCED_TO);
+    let proof_arg = RawArg::get(ARG_PROOF);
+    let (_, proof) = batch_proof(&proof_arg);
+    let inputs = PublicInputs {
+        prev_root,
+        new_root,
+        forced_acc_from: forced::accumulator(forced_from),
+        forced_acc_to: forced::accumulator(forced_to),
+    };
+    let batch_hash = bounties::batch_hash(&inputs).unwrap_or_revert_with(Error::InvalidArgument);
+    let commitment = bounties::claim_commitment(batch_hash, &prover, proof_arg.as_bytes())
+        .unwrap_or_revert_with(Error::Serialization);
+    bounties::check_reveal(
+        bounties::committed_at(commitment),
+        runtime::get_block_height(),
+    )
+    .unwrap_or_else(|e| runtime::revert(e));
+    let reward = bounties::take(batch_hash);
+    if !verifier::verify(&proof, &inputs) {
+        runtime::revert(Error::InvalidProof);
+    }
+
+    pay_out(bounties::fee_purse(), reward, recipient);
+    events::emit(&BountyClaimed {
+        batch_hash,
+        prover,
+        reward,
+        proof: Bytes::from(proof_arg.as_bytes().to_vec()),
+    });
+    runtime::ret(CLValue::from_t(reward).unwrap_or_revert_with(Error::Serialization));
 }
 
 /// Submit a batch - updates state root after ZK proof verification
 #[no_mangle]
 pub extern "C" fn submit_batch() {
+    stats::hit(EP_SUBMIT_BATCH);
+    require_not_paused();
+    roles::require_sequencer();
+
     // Get arguments
     let new_root: U512 = runtime::get_named_arg(ARG_ROOT);
-    let _proof: U512 = runtime::get_named_arg(ARG_PROOF);
+    let proof_arg = RawArg::get(ARG_PROOF);
+    let proof_len = proof_arg.as_bytes().len() as u64;
+    let (proof_system, proof) = batch_proof(&proof_arg);
+    let forced_from: u64 = runtime::get_named_arg(ARG_FORCED_FROM);
+    let forced_to: u64 = runtime::get_named_arg(ARG_FORCED_TO);
+    let tx_count: u64 = runtime::get_named_arg(ARG_TX_COUNT);
+
+    // Advance the forced queue; reverts if a forced withdrawal is overdue
+    let current: u64 = store::read_key(KEY_BATCH_COUNT);
+    let (forced_acc_from, forced_acc_to) = forced::consume(forced_from, forced_to, current);
 
-    // TODO: Verify ZK proof on-chain (future enhancement)
-    // For now, we trust the sequencer's proof
+    // Record what the batch claims, including the forced range
+    let prev_root: U512 = store::read_key(KEY_STATE_ROOT);
+    let inputs = PublicInputs {
+        prev_root,
+        new_root,
+        forced_acc_from,
+        forced_acc_to,
+    };
+    public_inputs::record(&inputs);
+
+    // Only accept the batch if the proof attests to this root transition
+    // over exactly the forced range the queue requires
+    if !verifier::verify(&proof, &inputs) {
+        runtime::revert(Error::InvalidProof);
+    }
+    stats::record_proof(proof_len);
 
     // Update state root
-    let root_key = runtime::get_key(KEY_STATE_ROOT).unwrap_or_revert();
-    let root_uref = root_key.into_uref().unwrap_or_revert();
-    storage::write(root_uref, new_root);
+    store::write_key(KEY_STATE_ROOT, new_root);
 
     // Increment batch counter
-    let count_key = runtime::get_key(KEY_BATCH_COUNT).unwrap_or_revert();
-    let count_uref = count_key.into_uref().unwrap_or_revert();
-    let current: u64 = storage::read(count_uref).unwrap_or_revert().unwrap_or_revert();
-    storage::write(count_uref, current + 1);
+    store::write_key(KEY_BATCH_COUNT, current + 1);
+
+    // Keep the full record so disputes can point at a specific batch, with
+    // the block time and height so it can be aligned with L1 eras off-chain
+    batches::record(BatchRecord {
+        root: new_root,
+        prev_root,
+        tx_count,
+        timestamp: u64::from(runtime::get_blocktime()),
+        block_height: runtime::get_block_height(),
+        proposer: runtime::get_caller(),
+        proof_system,
+    });
+
+    // Audit the raw arguments, proof bytes last
+    let mut args = (new_root, forced_from, forced_to, tx_count, proof_system)
+        .to_bytes()
+        .unwrap_or_revert_with(Error::Serialization);
+    args.extend_from_slice(proof_arg.as_bytes());
+    audit::record(AuditAction::SubmitBatch, &args);
+
+    events::emit(&BatchSubmitted {
+        batch_index: current,
+        root: new_root,
+    });
+}
+
+/// Queue a forced withdrawal of `amount` from `l2_address`
+/// The sequencer must process it within the inclusion window
+/// Only the account that registered the address with deposit_with_key may
+/// request, and only one request per address may wait at a time
+#[no_mangle]
+pub extern "C" fn request_l2_withdrawal() {
+    stats::hit(EP_REQUEST_L2_WITHDRAWAL);
+    let requester = runtime::get_caller();
+    screening::check(requester);
+
+    let l2_address: String = runtime::get_named_arg(ARG_L2_ADDRESS);
+    let amount: U512 = runtime::get_named_arg(ARG_AMOUNT);
+    if amount.is_zero() {
+        runtime::revert(Error::InvalidArgument);
+    }
+    canary::check_withdrawal(amount);
+    let owner = TypedDict::<String, AccountHash>::open(DICT_L2_OWNERS).get(&l2_address);
+    if owner != Some(requester) {
+        runtime::revert(Error::NotL2Owner);
+    }
+
+    let queued_at_batch: u64 = store::read_key(KEY_BATCH_COUNT);
+    let index = forced::enqueue(ForcedTx {
+        requester,
+        l2_address: l2_address.clone(),
+        amount,
+        queued_at_batch,
+    });
+    events::emit(&ForcedWithdrawalRequested {
+        index,
+        requester,
+        l2_address,
+        amount,
+    });
+}
+
+/// Block time (ms since epoch) at which a batch was accepted
+#[no_mangle]
+pub extern "C" fn get_batch_time() {
+    let batch_index: u64 = runtime::get_named_arg(ARG_BATCH_INDEX);
+    let record = batches::get(batch_index).unwrap_or_revert_with(Error::MissingValue);
+    runtime::ret(CLValue::from_t(record.timestamp).unwrap_or_revert_with(Error::Serialization));
+}
+
+/// Status tag of a queued withdrawal, see `WithdrawalStatus`
+/// Arguments: withdrawal_index (u64)
+#[no_mangle]
+pub extern "C" fn get_withdrawal_status() {
+    let index: u64 = runtime::get_named_arg(ARG_WITHDRAWAL_INDEX);
+    let status = withdrawals::status(index);
+    runtime::ret(CLValue::from_t(status.tag()).unwrap_or_revert_with(Error::Serialization));
 }
 
 /// Get current contract state (view function)
@@ -172,79 +950,186 @@ pub extern "C" fn get_state() {
     // Returns nothing but allows checking contract is callable
 }
 
+/// Completed calls to an entry point, for spotting abuse from chain state
+/// Failed calls are not counted; see stats.rs
+#[no_mangle]
+pub extern "C" fn get_stats() {
+    let entry_point: String = runtime::get_named_arg(ARG_ENTRY_POINT);
+    let count = stats::get(&entry_point);
+    runtime::ret(CLValue::from_t(count).unwrap_or_revert_with(Error::Serialization));
+}
+
+/// Accepted batch proofs: (count, total bytes, largest in bytes)
+#[no_mangle]
+pub extern "C" fn get_proof_stats() {
+    let stats = stats::proof_stats();
+    runtime::ret(CLValue::from_t(stats).unwrap_or_revert_with(Error::Serialization));
+}
+
+/// Set address screening mode: 0 = disabled, 1 = deny list, 2 = allow list
+#[no_mangle]
+pub extern "C" fn set_screening_mode() {
+    stats::hit(EP_SET_SCREENING_MODE);
+    let compliance = screening::require_compliance();
+    let mode: ScreeningMode = runtime::get_named_arg(ARG_MODE);
+    screening::set_mode(mode);
+    let args = mode.to_bytes().unwrap_or_revert_with(Error::Serialization);
+    audit::record(AuditAction::SetScreeningMode, &args);
+    events::emit(&ConfigChanged {
+        key: String::from(screening::KEY_SCREENING_MODE),
+        by: compliance,
+    });
+}
+
+/// Add or remove an account on the screening list
+#[no_mangle]
+pub extern "C" fn set_screened() {
+    stats::hit(EP_SET_SCREENED);
+    let compliance = screening::require_compliance();
+    let account: AccountHash = runtime::get_named_arg(ARG_ACCOUNT);
+    let listed: bool = runtime::get_named_arg(ARG_LISTED);
+    screening::set_listed(account, listed);
+    audit::record(
+        AuditAction::SetScreened,
+        &(account, listed).to_bytes().unwrap_or_revert_with(Error::Serialization),
+    );
+    events::emit(&ConfigChanged {
+        key: String::from(screening::DICT_SCREENING_LIST),
+        by: compliance,
+    });
+}
+
+/// Hand the compliance role to another account
+#[no_mangle]
+pub extern "C" fn set_compliance() {
+    stats::hit(EP_SET_COMPLIANCE);
+    let compliance = screening::require_compliance();
+    let account: AccountHash = runtime::get_named_arg(ARG_ACCOUNT);
+    screening::set_compliance(account);
+    let args = account.to_bytes().unwrap_or_revert_with(Error::Serialization);
+    audit::record(AuditAction::SetCompliance, &args);
+    events::emit(&ConfigChanged {
+        key: String::from(screening::KEY_COMPLIANCE),
+        by: compliance,
+    });
+}
+
+/// Publish the sequencer's RPC URL, signing key and API version for wallets
+#[no_mangle]
+pub extern "C" fn set_endpoint_metadata() {
+    stats::hit(EP_SET_ENDPOINT_METADATA);
+    let sequencer = roles::require_sequencer();
+    let rpc_url: String = runtime::get_named_arg(ARG_RPC_URL);
+    let public_key: Bytes = runtime::get_named_arg(ARG_PUBLIC_KEY);
+    let api_version: u32 = runtime::get_named_arg(ARG_API_VERSION);
+    if rpc_url.is_empty() || public_key.is_empty() {
+        runtime::revert(Error::InvalidArgument);
+    }
+
+    let args = (rpc_url.clone(), public_key.clone(), api_version)
+        .to_bytes()
+        .unwrap_or_revert_with(Error::Serialization);
+    endpoint::set(EndpointMetadata {
+        rpc_url,
+        public_key,
+        api_version,
+    });
+    audit::record(AuditAction::SetEndpointMetadata, &args);
+    events::emit(&ConfigChanged {
+        key: String::from(endpoint::KEY_ENDPOINT_METADATA),
+        by: sequencer,
+    });
+}
+
+/// Hand the sequencer role to another account
+#[no_mangle]
+pub extern "C" fn set_sequencer() {
+    stats::hit(EP_SET_SEQUENCER);
+    let admin = roles::require_admin();
+    let account: AccountHash = runtime::get_named_arg(ARG_ACCOUNT);
+    roles::set_sequencer(account);
+    let args = account.to_bytes().unwrap_or_revert_with(Error::Serialization);
+    audit::record(AuditAction::SetSequencer, &args);
+    events::emit(&ConfigChanged {
+        key: String::from(roles::KEY_SEQUENCER),
+        by: admin,
+    });
+}
+
+/// Revert with `Error::Paused` while the bridge is halted
+fn require_not_paused() {
+    let paused: bool = store::read_key(KEY_PAUSED);
+    if paused {
+        runtime::revert(Error::Paused);
+    }
+}
+
+/// Halt deposits, withdrawals and batch submission
+#[no_mangle]
+pub extern "C" fn pause() {
+    stats::hit(EP_PAUSE);
+    set_paused(true);
+}
+
+/// Resume a paused bridge
+#[no_mangle]
+pub extern "C" fn unpause() {
+    stats::hit(EP_UNPAUSE);
+    set_paused(false);
+}
+
+fn set_paused(paused: bool) {
+    let admin = roles::require_admin();
+    store::write_key(KEY_PAUSED, paused);
+    let args = paused.to_bytes().unwrap_or_revert_with(Error::Serialization);
+    audit::record(AuditAction::SetPaused, &args);
+    events::emit(&Paused { paused, by: admin });
+}
+
 /// Contract installation
 #[no_mangle]
 pub extern "C" fn call() {
-    let mut entry_points = EntryPoints::new();
-
-    // init() - no parameters
-    entry_points.add_entry_point(EntityEntryPoint::new(
-        EP_INIT,
-        vec![],
-        CLType::Unit,
-        EntryPointAccess::Public,
-        EntryPointType::Called,
-        EntryPointPayment::Caller,
-    ));
-
-    // deposit(amount: U512, purse: URef, l2_address: String)
-    entry_points.add_entry_point(EntityEntryPoint::new(
-        EP_DEPOSIT,
-        vec![
-            Parameter::new(ARG_AMOUNT, CLType::U512),
-            Parameter::new(ARG_PURSE, CLType::URef),
-            Parameter::new(ARG_L2_ADDRESS, CLType::String),
-        ],
-        CLType::Unit,
-        EntryPointAccess::Public,
-        EntryPointType::Called,
-        EntryPointPayment::Caller,
-    ));
-
-    // withdraw(amount: U512, proof: U512, recipient: URef)
-    entry_points.add_entry_point(EntityEntryPoint::new(
-        EP_WITHDRAW,
-        vec![
-            Parameter::new(ARG_AMOUNT, CLType::U512),
-            Parameter::new(ARG_PROOF, CLType::U512),
-            Parameter::new(ARG_RECIPIENT, CLType::URef),
-        ],
-        CLType::Unit,
-        EntryPointAccess::Public,
-        EntryPointType::Called,
-        EntryPointPayment::Caller,
-    ));
-
-    // submit_batch(root: U512, proof: U512)
-    entry_points.add_entry_point(EntityEntryPoint::new(
-        EP_SUBMIT_BATCH,
-        vec![
-            Parameter::new(ARG_ROOT, CLType::U512),
-            Parameter::new(ARG_PROOF, CLType::U512),
-        ],
-        CLType::Unit,
-        EntryPointAccess::Public,
-        EntryPointType::Called,
-        EntryPointPayment::Caller,
-    ));
-
-    // get_state() - read-only
-    entry_points.add_entry_point(EntityEntryPoint::new(
-        EP_GET_STATE,
-        vec![],
-        CLType::Unit,
-        EntryPointAccess::Public,
-        EntryPointType::Called,
-        EntryPointPayment::Caller,
-    ));
+    let entry_points = abi::entry_points(ENTRY_POINTS);
+
+    let mut message_topics = BTreeMap::new();
+    for topic in events::TOPICS {
+        message_topics.insert(String::from(*topic), MessageTopicOperation::Add);
+    }
 
     let (contract_hash, _) = storage::new_contract(
         entry_points,
         None,
         Some(String::from(CONTRACT_HASH_NAME)),
         Some(String::from(CONTRACT_PACKAGE_NAME)),
-        None,
+        Some(message_topics),
     );
 
     runtime::put_key(CONTRACT_HASH_NAME, contract_hash.into());
+
+    // Initialize and verify in the same deploy, so a partial install reverts
+    let verifying_key: Bytes = runtime::get_named_arg(ARG_VERIFYING_KEY);
+    let circuit_id: [u8; 32] = runtime::get_named_arg(ARG_CIRCUIT_ID);
+    let package = runtime::get_key(CONTRACT_PACKAGE_NAME).unwrap_or_revert_with(Error::MissingKey);
+    let mut init_args = runtime_args! {
+        ARG_VERIFYING_KEY => verifying_key,
+        ARG_CIRCUIT_ID => circuit_id,
+        ARG_CONTRACT_PACKAGE_HASH => package,
+    };
+    // Canary limits are only forwarded when the installer supplies them
+    if let Some(arg) = RawArg::try_get(ARG_CANARY_TVL_CAP) {
+        init_args
+            .insert(ARG_CANARY_TVL_CAP, arg.parse::<U512>())
+            .unwrap_or_revert_with(Error::Serialization);
+    }
+    if let Some(arg) = RawArg::try_get(ARG_CANARY_EXPIRY_HEIGHT) {
+        init_args
+            .insert(ARG_CANARY_EXPIRY_HEIGHT, arg.parse::<u64>())
+            .unwrap_or_revert_with(Error::Serialization);
+    }
+    runtime::call_contract::<()>(contract_hash, EP_INIT, init_args);
+    runtime::call_contract::<()>(contract_hash, EP_SELF_CHECK, RuntimeArgs::new());
+
+    // Publish the ABI next to the contract hash so clients can build deploys from it
+    let abi_uref = storage::new_uref(abi::to_json(ENTRY_POINTS, Error::ALL));
+    runtime::put_key(CONTRACT_ABI_NAME, abi_uref.into());
 }
@@ -1,6 +1,7 @@
 #![no_std]
 #![no_main]
 #![feature(core_intrinsics)]
+#![allow(internal_features)]
 
 extern crate alloc;
 
@@ -14,16 +15,14 @@ fn panic(_info: &core::panic::PanicInfo) -> ! {
     core::intrinsics::abort()
 }
 
+#[allow(dead_code)]
+mod abi;
+
 use casper_contract::contract_api::{runtime, storage};
 use casper_contract::unwrap_or_revert::UnwrapOrRevert;
-use alloc::vec;
 use alloc::string::String;
-use casper_types::{
-    CLType,
-    addressable_entity::{
-        EntryPointAccess, EntryPointType, EntryPoints, EntityEntryPoint, EntryPointPayment,
-    },
-};
+
+use abi::{Access, ArgType, EntryPointDef};
 
 // Contract constants
 const CONTRACT_HASH_NAME: &str = "casper_accelerate_contract_hash";
@@ -37,24 +36,20 @@ pub extern "C" fn get_version() {
     runtime::ret(casper_types::CLValue::from_t(1u32).unwrap_or_revert());
 }
 
+/// A single minimal entry point
+const ENTRY_POINTS: &[EntryPointDef] = &[EntryPointDef {
+    name: ENTRY_POINT_GET_VERSION,
+    args: &[],
+    ret: ArgType::U32,
+    access: Access::Public,
+}];
+
 /// Contract installation entry point
 #[no_mangle]
 pub extern "C" fn call() {
-    // Create a minimal entry point
-    let mut entry_points = EntryPoints::new();
-
-    entry_points.add_entry_point(EntityEntryPoint::new(
-        ENTRY_POINT_GET_VERSION,
-        vec![],
-        CLType::U32,
-        EntryPointAccess::Public,
-        EntryPointType::Called,
-        EntryPointPayment::Caller,
-    ));
-
     // Create contract with no named keys (simplest possible)
     let (contract_hash, _version) = storage::new_contract(
-        entry_points,
+        abi::entry_points(ENTRY_POINTS),
         None,
         Some(String::from(CONTRACT_HASH_NAME)),
         Some(String::from(CONTRACT_PACKAGE_HASH_NAME)),
@@ -1,6 +1,7 @@
 #![no_std]
 #![no_main]
 #![feature(core_intrinsics)]
+#![allow(internal_features)]
 
 extern crate alloc;
 
@@ -14,12 +15,13 @@ fn panic(_info: &core::panic::PanicInfo) -> ! {
     core::intrinsics::abort()
 }
 
+#[allow(dead_code)]
+mod abi;
+
 use casper_contract::contract_api::{runtime, storage};
-use alloc::vec;
 use alloc::string::String;
-use casper_types::{
-    CLType, EntryPoint, EntryPointAccess, EntryPointType, EntryPoints,
-};
+
+use abi::{Access, ArgType, EntryPointDef};
 
 const CONTRACT_HASH_NAME: &str = "accelerate_hash";
 const CONTRACT_PACKAGE_NAME: &str = "accelerate_pkg";
@@ -36,32 +38,31 @@ pub extern "C" fn entry_two() {
     // Do nothing - simplest possible entry point
 }
 
+/// Entry point declarations
+const ENTRY_POINTS: &[EntryPointDef] = &[
+    EntryPointDef {
+        name: "entry_one",
+        args: &[],
+        ret: ArgType::Unit,
+        access: Access::Public,
+    },
+    EntryPointDef {
+        name: "entry_two",
+        args: &[],
+        ret: ArgType::Unit,
+        access: Access::Public,
+    },
+];
+
 /// Contract installation
 #[no_mangle]
 pub extern "C" fn call() {
-    let mut entry_points = EntryPoints::new();
-
-    entry_points.add_entry_point(EntryPoint::new(
-        "entry_one",
-        vec![],
-        CLType::Unit,
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
-
-    entry_points.add_entry_point(EntryPoint::new(
-        "entry_two",
-        vec![],
-        CLType::Unit,
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
-
     let (contract_hash, _) = storage::new_contract(
-        entry_points,
+        abi::entry_points(ENTRY_POINTS),
         None,
         Some(String::from(CONTRACT_HASH_NAME)),
         Some(String::from(CONTRACT_PACKAGE_NAME)),
+        None, // message_topics - new in SDK v5
     );
 
     runtime::put_key(CONTRACT_HASH_NAME, contract_hash.into());
@@ -0,0 +1,286 @@
+//! Fast-exit liquidity pool
+//!
+//! Providers deposit CSPR for pool shares. A user with a pending bridge
+//! withdrawal signs an assignment of it to the pool purse (see the bridge's
+//! `assignment` module); `front_withdrawal` presents it through the bridge's
+//! `claim_to` and pays the user at once, less the pool fee. After the
+//! challenge window anyone can `settle` the withdrawal, which finalizes it
+//! into the pool purse. Fees accrue to providers through the share price;
+//! see `shares`.
+//!
+//! A fronted withdrawal whose root is disputed and cancelled is written off
+//! when settled. If its leaf is proven again the bridge still pays the pool,
+//! which shows up as a gain once that withdrawal is finalized.
+//!
+//! Install after the bridge with `bridge` set to its package hash.
+
+#![no_std]
+#![no_main]
+#![feature(core_intrinsics)]
+#![allow(internal_features)]
+
+extern crate alloc;
+
+#[cfg(not(test))]
+#[global_allocator]
+static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
+
+#[cfg(not(test))]
+#[panic_handler]
+fn panic(_info: &core::panic::PanicInfo) -> ! {
+    core::intrinsics::abort()
+}
+
+#[allow(dead_code)]
+mod abi;
+#[allow(dead_code)]
+mod entry_points;
+#[allow(dead_code)]
+mod error;
+mod lp_pool_entry_points;
+#[allow(dead_code)]
+mod network;
+mod shares;
+#[allow(dead_code)]
+mod store;
+
+use alloc::string::String;
+use casper_accelerate_shared::{hash, types::WithdrawalStatus};
+use casper_contract::{
+    contract_api::{runtime, storage, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    account::AccountHash,
+    bytesrepr::{Bytes, FromBytes, ToBytes},
+    contracts::ContractPackageHash,
+    runtime_args, CLTyped, CLValue, Key, PublicKey, RuntimeArgs, URef, U512,
+};
+
+use entry_points::{
+    ARG_AMOUNT, ARG_PUBLIC_KEY, ARG_PURSE, ARG_RECIPIENT, ARG_SIGNATURE, ARG_WITHDRAWAL_INDEX,
+    EP_CLAIM_TO, EP_FINALIZE_WITHDRAWAL, EP_GET_WITHDRAWAL_STATUS, EP_REACTIVATE_WITHDRAWAL,
+};
+use error::Error;
+use lp_pool_entry_points::*;
+use network::{LP_POOL_HASH_NAME, LP_POOL_PACKAGE_NAME};
+use store::TypedDict;
+
+const KEY_BRIDGE: &str = "bridge";
+const KEY_FEE_BPS: &str = "fee_bps";
+const KEY_POOL_PURSE: &str = "pool_purse";
+const KEY_TOTAL_SHARES: &str = "total_shares";
+/// Sum of fronted withdrawals not yet settled
+const KEY_OUTSTANDING: &str = "outstanding";
+const DICT_SHARES: &str = "shares";
+/// Amount fronted, by bridge withdrawal index
+const DICT_FRONTED: &str = "fronted";
+
+fn shares() -> TypedDict<AccountHash, U512> {
+    TypedDict::open(DICT_SHARES)
+}
+
+fn fronted() -> TypedDict<u64, U512> {
+    TypedDict::open(DICT_FRONTED)
+}
+
+fn pool_purse() -> URef {
+    store::key_uref(KEY_POOL_PURSE)
+}
+
+fn purse_balance() -> U512 {
+    system::get_purse_balance(pool_purse()).unwrap_or_revert_with(Error::MissingValue)
+}
+
+fn total_assets_now() -> U512 {
+    let outstanding: U512 = store::read_key(KEY_OUTSTANDING);
+    purse_balance() + outstanding
+}
+
+fn bridge() -> ContractPackageHash {
+    store::read_key(KEY_BRIDGE)
+}
+
+fn call_bridge<T: CLTyped + FromBytes>(entry_point: &str, args: RuntimeArgs) -> T {
+    runtime::call_versioned_contract(bridge(), None, entry_point, args)
+}
+
+fn ret<T: CLTyped + ToBytes>(value: T) -> ! {
+    runtime::ret(CLValue::from_t(value).unwrap_or_revert_with(Error::Serialization))
+}
+
+/// Pay `amount` from the pool purse to `account`
+fn pay(account: AccountHash, amount: U512) {
+    if purse_balance() < amount {
+        runtime::revert(Error::InsufficientLiquidity);
+    }
+    system::transfer_from_purse_to_account(pool_purse(), account, amount, None)
+        .unwrap_or_revert_with(Error::WithdrawalTransferFailed);
+}
+
+/// Create pool storage
+/// Arguments: bridge (Key, the bridge's package hash), fee_bps (u32)
+#[no_mangle]
+pub extern "C" fn init() {
+    if runtime::has_key(KEY_TOTAL_SHARES) {
+        runtime::revert(Error::AlreadyInitialized);
+    }
+    let bridge: Key = runtime::get_named_arg(ARG_BRIDGE);
+    let bridge = bridge
+        .into_hash_addr()
+        .map(ContractPackageHash::new)
+        .unwrap_or_revert_with(Error::InvalidArgument);
+    let fee_bps: u32 = runtime::get_named_arg(ARG_FEE_BPS);
+    if fee_bps > shares::BPS {
+        runtime::revert(Error::InvalidArgument);
+    }
+    store::new_key(KEY_BRIDGE, bridge);
+    store::new_key(KEY_FEE_BPS, fee_bps);
+    store::new_key(KEY_TOTAL_SHARES, U512::zero());
+    store::new_key(KEY_OUTSTANDING, U512::zero());
+    runtime::put_key(KEY_POOL_PURSE, system::create_purse().into());
+    TypedDict::<AccountHash, U512>::create(DICT_SHARES);
+    TypedDict::<u64, U512>::create(DICT_FRONTED);
+}
+
+/// Deposit CSPR from `purse` and return the shares minted to the caller
+/// Arguments: amount (U512), purse (URef)
+#[no_mangle]
+pub extern "C" fn provide() {
+    let amount: U512 = runtime::get_named_arg(ARG_AMOUNT);
+    let purse: URef = runtime::get_named_arg(ARG_PURSE);
+    if amount.is_zero() {
+        runtime::revert(Error::InvalidAmount);
+    }
+    let total_shares: U512 = store::read_key(KEY_TOTAL_SHARES);
+    let minted = shares::to_shares(amount, total_shares, total_assets_now())
+        .unwrap_or_revert_with(Error::InvalidAmount);
+    system::transfer_from_purse_to_purse(purse, pool_purse(), amount, None)
+        .unwrap_or_revert_with(Error::DepositTransferFailed);
+
+    let provider = runtime::get_caller();
+    let held = shares().get(&provider).unwrap_or_default();
+    shares().set(&provider, held + minted);
+    store::write_key(KEY_TOTAL_SHARES, total_shares + minted);
+    ret(minted)
+}
+
+/// Redeem the caller's shares and return the CSPR paid out
+///
+/// Only the purse balance can be paid, so this reverts while too much of
+/// the pool is out fronting withdrawals.
+/// Arguments: shares (U512)
+#[no_mangle]
+pub extern "C" fn withdraw_liquidity() {
+    let redeemed: U512 = runtime::get_named_arg(ARG_SHARES);
+    let provider = runtime::get_caller();
+    let held = shares().get(&provider).unwrap_or_default();
+    let remaining = held
+        .checked_sub(redeemed)
+        .unwrap_or_revert_with(Error::InsufficientShares);
+    let total_shares: U512 = store::read_key(KEY_TOTAL_SHARES);
+    let payout = shares::to_assets(redeemed, total_shares, total_assets_now())
+        .unwrap_or_revert_with(Error::InsufficientShares);
+
+    shares().set(&provider, remaining);
+    store::write_key(KEY_TOTAL_SHARES, total_shares - redeemed);
+    pay(provider, payout);
+    ret(payout)
+}
+
+/// Take over a pending withdrawal assigned to the pool and pay its recipient
+/// now, less the fee; returns the amount paid
+/// Arguments: withdrawal_index (u64), public_key (PublicKey of the
+/// recipient), signature (Bytes, the recipient's assignment to the pool
+/// purse)
+#[no_mangle]
+pub extern "C" fn front_withdrawal() {
+    let index: u64 = runtime::get_named_arg(ARG_WITHDRAWAL_INDEX);
+    let public_key: PublicKey = runtime::get_named_arg(ARG_PUBLIC_KEY);
+    let signature: Bytes = runtime::get_named_arg(ARG_SIGNATURE);
+
+    let amount: U512 = call_bridge(
+        EP_CLAIM_TO,
+        runtime_args! {
+            ARG_WITHDRAWAL_INDEX => index,
+            ARG_RECIPIENT => Key::URef(pool_purse().into_add()),
+            ARG_PUBLIC_KEY => public_key.clone(),
+            ARG_SIGNATURE => signature,
+        },
+    );
+    let fee_bps: u32 = store::read_key(KEY_FEE_BPS);
+    let fee = shares::fee(amount, fee_bps).unwrap_or_revert_with(Error::InvalidAmount);
+    let payout = amount - fee;
+    pay(public_key.to_account_hash(), payout);
+
+    fronted().set(&index, amount);
+    let outstanding: U512 = store::read_key(KEY_OUTSTANDING);
+    store::write_key(KEY_OUTSTANDING, outstanding + amount);
+    ret(payout)
+}
+
+/// Collect a fronted withdrawal from the bridge, or write it off if the
+/// bridge cancelled it
+/// Arguments: withdrawal_index (u64)
+#[no_mangle]
+pub extern "C" fn settle() {
+    let index: u64 = runtime::get_named_arg(ARG_WITHDRAWAL_INDEX);
+    let amount = fronted()
+        .get(&index)
+        .unwrap_or_revert_with(Error::NotFronted);
+
+    let tag: u8 = call_bridge(
+        EP_GET_WITHDRAWAL_STATUS,
+        runtime_args! { ARG_WITHDRAWAL_INDEX => index },
+    );
+    let args = runtime_args! { ARG_WITHDRAWAL_INDEX => index };
+    match WithdrawalStatus::from_tag(tag) {
+        Some(WithdrawalStatus::Pending) => call_bridge(EP_FINALIZE_WITHDRAWAL, args),
+        Some(WithdrawalStatus::Expired) => {
+            call_bridge::<()>(EP_REACTIVATE_WITHDRAWAL, args.clone());
+            call_bridge(EP_FINALIZE_WITHDRAWAL, args)
+        }
+        // Paid into the pool purse by whoever finalized it, or written off
+        Some(WithdrawalStatus::Claimed) | Some(WithdrawalStatus::Cancelled) => {}
+        _ => runtime::revert(Error::WithdrawalNotPending),
+    }
+
+    fronted().remove(&index);
+    let outstanding: U512 = store::read_key(KEY_OUTSTANDING);
+    store::write_key(KEY_OUTSTANDING, outstanding - amount);
+}
+
+/// Arguments: address (AccountHash)
+#[no_mangle]
+pub extern "C" fn shares_of() {
+    let address: AccountHash = runtime::get_named_arg(ARG_ADDRESS);
+    ret(shares().get(&address).unwrap_or_default())
+}
+
+/// Purse balance plus fronted withdrawals not yet settled
+#[no_mangle]
+pub extern "C" fn total_assets() {
+    ret(total_assets_now())
+}
+
+/// Pool installation
+/// Arguments: bridge (Key, the bridge's contract package hash), fee_bps (u32)
+#[no_mangle]
+pub extern "C" fn call() {
+    let (contract_hash, _) = storage::new_contract(
+        abi::entry_points(ENTRY_POINTS),
+        None,
+        Some(String::from(LP_POOL_PACKAGE_NAME)),
+        None,
+        None,
+    );
+    runtime::put_key(LP_POOL_HASH_NAME, contract_hash.into());
+
+    let bridge: Key = runtime::get_named_arg(ARG_BRIDGE);
+    let fee_bps: u32 = runtime::get_named_arg(ARG_FEE_BPS);
+    runtime::call_contract::<()>(
+        contract_hash,
+        EP_INIT,
+        runtime_args! { ARG_BRIDGE => bridge, ARG_FEE_BPS => fee_bps },
+    );
+}
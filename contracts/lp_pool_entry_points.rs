@@ -0,0 +1,83 @@
+//! Entry points of the fast-exit liquidity pool (see `lp_pool.rs`)
+//!
+//! Arguments the pool forwards to the bridge keep the bridge's names.
+
+use crate::{
+    abi::{arg, Access, ArgType, EntryPointDef},
+    entry_points::{ARG_AMOUNT, ARG_PUBLIC_KEY, ARG_PURSE, ARG_SIGNATURE, ARG_WITHDRAWAL_INDEX},
+};
+
+pub const EP_INIT: &str = "init";
+pub const EP_PROVIDE: &str = "provide";
+pub const EP_WITHDRAW_LIQUIDITY: &str = "withdraw_liquidity";
+pub const EP_FRONT_WITHDRAWAL: &str = "front_withdrawal";
+pub const EP_SETTLE: &str = "settle";
+pub const EP_SHARES_OF: &str = "shares_of";
+pub const EP_TOTAL_ASSETS: &str = "total_assets";
+
+pub const ARG_BRIDGE: &str = "bridge";
+pub const ARG_FEE_BPS: &str = "fee_bps";
+pub const ARG_SHARES: &str = "shares";
+pub const ARG_ADDRESS: &str = "address";
+
+pub const ENTRY_POINTS: &[EntryPointDef] = &[
+    // init(bridge: Key, fee_bps: u32)
+    EntryPointDef {
+        name: EP_INIT,
+        args: &[
+            arg(ARG_BRIDGE, ArgType::Key),
+            arg(ARG_FEE_BPS, ArgType::U32),
+        ],
+        ret: ArgType::Unit,
+        access: Access::Public,
+    },
+    // provide(amount: U512, purse: URef) -> U512
+    EntryPointDef {
+        name: EP_PROVIDE,
+        args: &[
+            arg(ARG_AMOUNT, ArgType::U512),
+            arg(ARG_PURSE, ArgType::URef),
+        ],
+        ret: ArgType::U512,
+        access: Access::Public,
+    },
+    // withdraw_liquidity(shares: U512) -> U512
+    EntryPointDef {
+        name: EP_WITHDRAW_LIQUIDITY,
+        args: &[arg(ARG_SHARES, ArgType::U512)],
+        ret: ArgType::U512,
+        access: Access::Public,
+    },
+    // front_withdrawal(withdrawal_index: u64, public_key: PublicKey, signature: Bytes) -> U512
+    EntryPointDef {
+        name: EP_FRONT_WITHDRAWAL,
+        args: &[
+            arg(ARG_WITHDRAWAL_INDEX, ArgType::U64),
+            arg(ARG_PUBLIC_KEY, ArgType::PublicKey),
+            arg(ARG_SIGNATURE, ArgType::Bytes),
+        ],
+        ret: ArgType::U512,
+        access: Access::Public,
+    },
+    // settle(withdrawal_index: u64)
+    EntryPointDef {
+        name: EP_SETTLE,
+        args: &[arg(ARG_WITHDRAWAL_INDEX, ArgType::U64)],
+        ret: ArgType::Unit,
+        access: Access::Public,
+    },
+    // shares_of(address: AccountHash) -> U512
+    EntryPointDef {
+        name: EP_SHARES_OF,
+        args: &[arg(ARG_ADDRESS, ArgType::AccountHash)],
+        ret: ArgType::U512,
+        access: Access::Public,
+    },
+    // total_assets() -> U512
+    EntryPointDef {
+        name: EP_TOTAL_ASSETS,
+        args: &[],
+        ret: ArgType::U512,
+        access: Access::Public,
+    },
+];
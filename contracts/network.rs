@@ -0,0 +1,74 @@
+//! Per-network constants, selected by cargo feature
+//!
+//! Build with exactly one of `testnet`, `mainnet` or `integration`; there is
+//! no default, so every artifact names its network explicitly.
+//! `networks.json` mirrors these values for off-chain tooling.
+
+#[cfg(any(
+    all(feature = "testnet", feature = "mainnet"),
+    all(feature = "testnet", feature = "integration"),
+    all(feature = "mainnet", feature = "integration"),
+))]
+compile_error!("enable exactly one network feature: testnet, mainnet or integration");
+
+#[cfg(not(any(feature = "testnet", feature = "mainnet", feature = "integration")))]
+compile_error!("enable one network feature: testnet, mainnet or integration");
+
+#[cfg(feature = "testnet")]
+mod consts {
+    pub const NETWORK: &str = "testnet";
+    pub const CHAIN_NAME: &str = "casper-test";
+    pub const CONTRACT_HASH_NAME: &str = "casper_accelerate_hash";
+    pub const CONTRACT_PACKAGE_NAME: &str = "casper_accelerate_package";
+    pub const CONTRACT_ABI_NAME: &str = "casper_accelerate_abi";
+    pub const WCSPR_HASH_NAME: &str = "wcspr_hash";
+    pub const WCSPR_PACKAGE_NAME: &str = "wcspr_package";
+    pub const LP_POOL_HASH_NAME: &str = "lp_pool_hash";
+    pub const LP_POOL_PACKAGE_NAME: &str = "lp_pool_package";
+    /// Batches a forced withdrawal may wait before submit_batch reverts
+    pub const FORCED_INCLUSION_WINDOW: u64 = 10;
+    /// Blocks a proven withdrawal waits before it can be finalized
+    pub const WITHDRAWAL_CHALLENGE_WINDOW: u64 = 1000;
+    /// Blocks an expired withdrawal can still be reactivated
+    pub const WITHDRAWAL_GRACE_WINDOW: u64 = 1000;
+}
+
+#[cfg(feature = "mainnet")]
+mod consts {
+    pub const NETWORK: &str = "mainnet";
+    pub const CHAIN_NAME: &str = "casper";
+    pub const CONTRACT_HASH_NAME: &str = "casper_accelerate_hash";
+    pub const CONTRACT_PACKAGE_NAME: &str = "casper_accelerate_package";
+    pub const CONTRACT_ABI_NAME: &str = "casper_accelerate_abi";
+    pub const WCSPR_HASH_NAME: &str = "wcspr_hash";
+    pub const WCSPR_PACKAGE_NAME: &str = "wcspr_package";
+    pub const LP_POOL_HASH_NAME: &str = "lp_pool_hash";
+    pub const LP_POOL_PACKAGE_NAME: &str = "lp_pool_package";
+    /// Batches a forced withdrawal may wait before submit_batch reverts
+    pub const FORCED_INCLUSION_WINDOW: u64 = 10;
+    /// Blocks a proven withdrawal waits before it can be finalized, about a week
+    pub const WITHDRAWAL_CHALLENGE_WINDOW: u64 = 75_600;
+    /// Blocks an expired withdrawal can still be reactivated, about 90 days
+    pub const WITHDRAWAL_GRACE_WINDOW: u64 = 972_000;
+}
+
+#[cfg(feature = "integration")]
+mod consts {
+    pub const NETWORK: &str = "integration";
+    pub const CHAIN_NAME: &str = "integration-test";
+    pub const CONTRACT_HASH_NAME: &str = "casper_accelerate_it_hash";
+    pub const CONTRACT_PACKAGE_NAME: &str = "casper_accelerate_it_package";
+    pub const CONTRACT_ABI_NAME: &str = "casper_accelerate_it_abi";
+    pub const WCSPR_HASH_NAME: &str = "wcspr_it_hash";
+    pub const WCSPR_PACKAGE_NAME: &str = "wcspr_it_package";
+    pub const LP_POOL_HASH_NAME: &str = "lp_pool_it_hash";
+    pub const LP_POOL_PACKAGE_NAME: &str = "lp_pool_it_package";
+    /// Batches a forced withdrawal may wait before submit_batch reverts
+    pub const FORCED_INCLUSION_WINDOW: u64 = 2;
+    /// Blocks a proven withdrawal waits before it can be finalized
+    pub const WITHDRAWAL_CHALLENGE_WINDOW: u64 = 2;
+    /// Blocks an expired withdrawal can still be reactivated
+    pub const WITHDRAWAL_GRACE_WINDOW: u64 = 2;
+}
+
+pub use consts::*;
@@ -0,0 +1,35 @@
+//! What each batch claimed
+//!
+//! The roots and forced-queue range of every accepted batch are the public
+//! inputs its proof is checked against (see `verifier`). They are also
+//! committed to as one blake2b-256 digest, recorded per batch so provers and
+//! watchers can check exactly what the contract accepted.
+
+use casper_contract::unwrap_or_revert::UnwrapOrRevert;
+
+pub use casper_accelerate_shared::public_inputs::PublicInputs;
+
+use crate::{error::Error, store::IndexedDict};
+
+const DICT_BATCH_INPUTS: &str = "batch_inputs";
+
+pub fn init() {
+    IndexedDict::<[u8; 32]>::create(DICT_BATCH_INPUTS);
+}
+
+/// Verify digest storage after install
+pub fn self_check() {
+    IndexedDict::<[u8; 32]>::require(DICT_BATCH_INPUTS);
+}
+
+/// Number of batches with a recorded digest
+pub fn len() -> u64 {
+    IndexedDict::<[u8; 32]>::open(DICT_BATCH_INPUTS).len()
+}
+
+/// Record the digest for the next batch and return it
+pub fn record(inputs: &PublicInputs) -> [u8; 32] {
+    let digest = inputs.digest().unwrap_or_revert_with(Error::Serialization);
+    IndexedDict::<[u8; 32]>::open(DICT_BATCH_INPUTS).push(digest);
+    digest
+}
@@ -0,0 +1,48 @@
+//! Operator roles
+//!
+//! The compliance role lives in `screening`; roles that run the rollup
+//! itself live here.
+
+use casper_contract::contract_api::runtime;
+use casper_types::account::AccountHash;
+
+use crate::{error::Error, store};
+
+pub const KEY_ADMIN: &str = "admin";
+pub const KEY_SEQUENCER: &str = "sequencer";
+
+/// Create role storage; the admin appoints and replaces the sequencer
+pub fn init(admin: AccountHash, sequencer: AccountHash) {
+    store::new_key(KEY_ADMIN, admin);
+    store::new_key(KEY_SEQUENCER, sequencer);
+}
+
+/// Verify role storage after install
+pub fn self_check() {
+    store::require_key(KEY_ADMIN);
+    store::require_key(KEY_SEQUENCER);
+}
+
+/// Revert unless the caller is the admin
+pub fn require_admin() -> AccountHash {
+    let caller = runtime::get_caller();
+    let admin: AccountHash = store::read_key(KEY_ADMIN);
+    if caller != admin {
+        runtime::revert(Error::Unauthorized);
+    }
+    caller
+}
+
+/// Revert unless the caller is the sequencer
+pub fn require_sequencer() -> AccountHash {
+    let caller = runtime::get_caller();
+    let sequencer: AccountHash = store::read_key(KEY_SEQUENCER);
+    if caller != sequencer {
+        runtime::revert(Error::Unauthorized);
+    }
+    caller
+}
+
+pub fn set_sequencer(account: AccountHash) {
+    store::write_key(KEY_SEQUENCER, account);
+}
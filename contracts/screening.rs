@@ -0,0 +1,75 @@
+//! Optional address screening for permissioned deployments
+//!
+//! Disabled by default. When enabled, `deposit` and `withdraw` check the
+//! caller against a list maintained by the compliance account.
+
+use casper_accelerate_shared::types::ScreeningMode;
+use casper_contract::contract_api::runtime;
+use casper_types::account::AccountHash;
+
+use crate::{
+    error::Error,
+    store::{self, TypedDict},
+};
+
+pub const KEY_SCREENING_MODE: &str = "screening_mode";
+pub const KEY_COMPLIANCE: &str = "compliance";
+pub const DICT_SCREENING_LIST: &str = "screening_list";
+
+fn list() -> TypedDict<AccountHash, bool> {
+    TypedDict::open(DICT_SCREENING_LIST)
+}
+
+/// Create screening storage, disabled, with `compliance` as the managing account
+pub fn init(compliance: AccountHash) {
+    store::new_key(KEY_SCREENING_MODE, ScreeningMode::Disabled);
+    store::new_key(KEY_COMPLIANCE, compliance);
+    TypedDict::<AccountHash, bool>::create(DICT_SCREENING_LIST);
+}
+
+/// Verify screening storage after install
+pub fn self_check() {
+    let _mode: ScreeningMode = store::read_key(KEY_SCREENING_MODE);
+    store::require_key(KEY_COMPLIANCE);
+    store::require_key(DICT_SCREENING_LIST);
+}
+
+/// Revert unless the caller is the compliance account
+pub fn require_compliance() -> AccountHash {
+    let caller = runtime::get_caller();
+    let compliance: AccountHash = store::read_key(KEY_COMPLIANCE);
+    if caller != compliance {
+        runtime::revert(Error::Unauthorized);
+    }
+    caller
+}
+
+pub fn set_mode(mode: ScreeningMode) {
+    store::write_key(KEY_SCREENING_MODE, mode);
+}
+
+pub fn set_listed(account: AccountHash, listed: bool) {
+    let list = list();
+    if listed {
+        list.set(&account, true);
+    } else {
+        list.remove(&account);
+    }
+}
+
+pub fn set_compliance(account: AccountHash) {
+    store::write_key(KEY_COMPLIANCE, account);
+}
+
+/// Revert with `Error::Screened` if `account` may not use the bridge
+pub fn check(account: AccountHash) {
+    let mode: ScreeningMode = store::read_key(KEY_SCREENING_MODE);
+    let allowed = match mode {
+        ScreeningMode::Disabled => true,
+        ScreeningMode::DenyList => !list().contains(&account),
+        ScreeningMode::AllowList => list().contains(&account),
+    };
+    if !allowed {
+        runtime::revert(Error::Screened);
+    }
+}
@@ -0,0 +1,90 @@
+//! Argument handling shared by the session wasm targets
+//!
+//! Every argument is read and validated before any purse is touched, and
+//! each failure reverts with its own `Error` code rather than the opaque
+//! host error a bare `get_named_arg` panic produces.
+
+use alloc::string::String;
+use casper_contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
+use casper_types::{
+    bytesrepr::{Bytes, FromBytes},
+    contracts::{ContractPackageHash, ContractVersion},
+    Key, URef, U512,
+};
+
+use crate::{args::RawArg, error::Error};
+
+pub const ARG_CONTRACT_PACKAGE_HASH: &str = "contract_package_hash";
+pub const ARG_CONTRACT_VERSION: &str = "contract_version";
+pub const ARG_AMOUNT: &str = "amount";
+pub const ARG_PURSE: &str = "purse";
+pub const ARG_L2_ADDRESS: &str = "l2_address";
+pub const ARG_L2_PUBLIC_KEY: &str = "l2_public_key";
+
+/// Read a named argument, reverting with `missing` or `invalid`
+pub fn named_arg<T: FromBytes>(name: &str, missing: Error, invalid: Error) -> T {
+    RawArg::try_get(name)
+        .unwrap_or_revert_with(missing)
+        .try_parse()
+        .unwrap_or_revert_with(invalid)
+}
+
+/// Package to call; unlike a contract hash this survives upgrades
+pub fn contract_package_hash() -> ContractPackageHash {
+    let key: Key = named_arg(
+        ARG_CONTRACT_PACKAGE_HASH,
+        Error::MissingContractPackageHash,
+        Error::InvalidContractPackageHash,
+    );
+    key.into_hash_addr()
+        .map(ContractPackageHash::new)
+        .unwrap_or_revert_with(Error::InvalidContractPackageHash)
+}
+
+/// Optional version pin; `None` calls the latest enabled version
+pub fn contract_version() -> Option<ContractVersion> {
+    RawArg::try_get(ARG_CONTRACT_VERSION).map(|raw| {
+        raw.try_parse::<ContractVersion>()
+            .unwrap_or_revert_with(Error::InvalidContractVersion)
+    })
+}
+
+pub fn amount() -> U512 {
+    let amount: U512 = named_arg(ARG_AMOUNT, Error::MissingAmount, Error::InvalidAmount);
+    if amount.is_zero() {
+        runtime::revert(Error::InvalidAmount);
+    }
+    amount
+}
+
+pub fn purse() -> URef {
+    named_arg(ARG_PURSE, Error::MissingPurse, Error::InvalidPurse)
+}
+
+/// Explicit `l2_address`, or one derived from the caller when it is omitted
+///
+/// The derived address is the caller's formatted account hash, which is
+/// itself a hash of the account public key, so the same account always
+/// deposits to the same L2 address without choosing one.
+pub fn l2_address() -> String {
+    let l2_address: String = match RawArg::try_get(ARG_L2_ADDRESS) {
+        Some(raw) => raw.try_parse().unwrap_or_revert_with(Error::InvalidL2Address),
+        None => runtime::get_caller().to_formatted_string(),
+    };
+    if l2_address.is_empty() {
+        runtime::revert(Error::InvalidL2Address);
+    }
+    l2_address
+}
+
+pub fn l2_public_key() -> Bytes {
+    let key: Bytes = named_arg(
+        ARG_L2_PUBLIC_KEY,
+        Error::MissingL2PublicKey,
+        Error::InvalidL2PublicKey,
+    );
+    if key.is_empty() {
+        runtime::revert(Error::InvalidL2PublicKey);
+    }
+    key
+}
@@ -0,0 +1,74 @@
+#![no_std]
+#![no_main]
+#![feature(core_intrinsics)]
+#![allow(internal_features)]
+
+#[cfg(not(target_arch = "wasm32"))]
+compile_error!("target arch should be wasm32: compile with '--target wasm32-unknown-unknown'");
+
+extern crate alloc;
+
+#[global_allocator]
+static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
+
+#[panic_handler]
+fn panic(_info: &core::panic::PanicInfo) -> ! {
+    core::intrinsics::abort()
+}
+
+#[allow(dead_code)]
+mod args;
+#[allow(dead_code)]
+mod error;
+#[allow(dead_code)]
+mod session;
+
+use casper_contract::contract_api::{account, runtime, system};
+use casper_contract::unwrap_or_revert::UnwrapOrRevert;
+use casper_types::runtime_args;
+
+use error::Error;
+use session::{ARG_AMOUNT, ARG_L2_ADDRESS, ARG_L2_PUBLIC_KEY, ARG_PURSE};
+
+const ENTRY_POINT_DEPOSIT_WITH_KEY: &str = "deposit_with_key";
+
+/// Fund a deposit and register the caller's L2 public key in one deploy
+/// Arguments: contract_package_hash (Key), contract_version (optional u32), amount (U512),
+/// l2_address (optional String), l2_public_key (Bytes)
+#[no_mangle]
+pub extern "C" fn call() {
+    // Validate every argument before touching any purse
+    let contract_package_hash = session::contract_package_hash();
+    let contract_version = session::contract_version();
+    let amount = session::amount();
+    let l2_address = session::l2_address();
+    let l2_public_key = session::l2_public_key();
+
+    let main_purse = account::get_main_purse();
+    let balance = system::get_purse_balance(main_purse).unwrap_or_revert_with(Error::InvalidPurse);
+    if balance < amount {
+        runtime::revert(Error::InsufficientBalance);
+    }
+
+    // Move exactly `amount` into a temporary purse so the contract never
+    // sees the caller's main purse. Transfers are exact integer motes and
+    // deposit_with_key takes exactly `amount` or reverts the whole deploy,
+    // so the purse is always empty afterwards: any wallet rounding happens
+    // when `amount` is chosen, before this session runs, and nothing is
+    // left over to refund.
+    let deposit_purse = system::create_purse();
+    system::transfer_from_purse_to_purse(main_purse, deposit_purse, amount, None)
+        .unwrap_or_revert_with(Error::DepositTransferFailed);
+
+    runtime::call_versioned_contract::<()>(
+        contract_package_hash,
+        contract_version,
+        ENTRY_POINT_DEPOSIT_WITH_KEY,
+        runtime_args! {
+            ARG_AMOUNT => amount,
+            ARG_PURSE => deposit_purse,
+            ARG_L2_ADDRESS => l2_address,
+            ARG_L2_PUBLIC_KEY => l2_public_key,
+        },
+    );
+}
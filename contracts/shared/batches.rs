@@ -0,0 +1,79 @@
+//! Per-batch metadata record
+//!
+//! The contract stores one `BatchRecord` per accepted batch in its `batches`
+//! dictionary, behind its layout version. Indexers and `accelerate-verify`
+//! decode the same type.
+
+use alloc::vec::Vec;
+use casper_types::{
+    account::AccountHash,
+    bytesrepr::{self, FromBytes, ToBytes},
+    U512,
+};
+
+use crate::{envelope::Versioned, types::ProofSystem};
+
+/// What the contract accepted for one batch
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BatchRecord {
+    /// State root after the batch
+    pub root: U512,
+    /// State root the batch was applied to
+    pub prev_root: U512,
+    /// L2 transactions the sequencer reports in the batch
+    pub tx_count: u64,
+    /// Block time (ms since epoch) the batch was accepted at
+    pub timestamp: u64,
+    /// Height of the block the batch was accepted in
+    pub block_height: u64,
+    /// Sequencer account that submitted the batch
+    pub proposer: AccountHash,
+    /// Proof system the batch was proven with
+    pub proof_system: ProofSystem,
+}
+
+impl Versioned for BatchRecord {
+    const VERSION: u8 = 1;
+
+    fn encoded_length(&self) -> usize {
+        self.root.serialized_length()
+            + self.prev_root.serialized_length()
+            + self.tx_count.serialized_length()
+            + self.timestamp.serialized_length()
+            + self.block_height.serialized_length()
+            + self.proposer.serialized_length()
+            + self.proof_system.serialized_length()
+    }
+
+    fn encode(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        self.root.write_bytes(writer)?;
+        self.prev_root.write_bytes(writer)?;
+        self.tx_count.write_bytes(writer)?;
+        self.timestamp.write_bytes(writer)?;
+        self.block_height.write_bytes(writer)?;
+        self.proposer.write_bytes(writer)?;
+        self.proof_system.write_bytes(writer)
+    }
+
+    fn decode(_version: u8, bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (root, rem) = U512::from_bytes(bytes)?;
+        let (prev_root, rem) = U512::from_bytes(rem)?;
+        let (tx_count, rem) = u64::from_bytes(rem)?;
+        let (timestamp, rem) = u64::from_bytes(rem)?;
+        let (block_height, rem) = u64::from_bytes(rem)?;
+        let (proposer, rem) = AccountHash::from_bytes(rem)?;
+        let (proof_system, rem) = ProofSystem::from_bytes(rem)?;
+        Ok((
+            BatchRecord {
+                root,
+                prev_root,
+                tx_count,
+                timestamp,
+                block_height,
+                proposer,
+                proof_system,
+            },
+            rem,
+        ))
+    }
+}
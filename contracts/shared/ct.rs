@@ -0,0 +1,64 @@
+//! Constant-time comparisons for roots, hashes and nullifiers
+//!
+//! Every byte is always inspected, so the work done does not depend on where
+//! two values first differ.
+
+/// Compare two 32-byte values in constant time
+#[inline(never)]
+pub fn eq_32(a: &[u8; 32], b: &[u8; 32]) -> bool {
+    eq(a, b)
+}
+
+/// Compare two slices in constant time; differing lengths compare unequal
+#[inline(never)]
+pub fn eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut diff = 0u8;
+    for (x, y) in a.iter().zip(b.iter()) {
+        diff |= x ^ y;
+    }
+    // Keep the optimizer from short-circuiting the loop
+    core::hint::black_box(diff) == 0
+}
+
+/// True if every byte is zero, e.g. an unset root
+#[inline(never)]
+pub fn is_zero(a: &[u8]) -> bool {
+    let mut acc = 0u8;
+    for x in a {
+        acc |= x;
+    }
+    core::hint::black_box(acc) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn equal_only_when_every_byte_matches() {
+        let a = [7u8; 32];
+        let mut b = a;
+        assert!(eq_32(&a, &b));
+        b[31] ^= 1;
+        assert!(!eq_32(&a, &b));
+        b = a;
+        b[0] ^= 0x80;
+        assert!(!eq_32(&a, &b));
+    }
+
+    #[test]
+    fn different_lengths_are_unequal() {
+        assert!(!eq(&[1, 2, 3], &[1, 2]));
+        assert!(eq(&[], &[]));
+    }
+
+    #[test]
+    fn zero_detection() {
+        assert!(is_zero(&[0; 32]));
+        assert!(!is_zero(&[0, 0, 1]));
+        assert!(is_zero(&[]));
+    }
+}
@@ -0,0 +1,214 @@
+//! Versioned storage envelopes
+//!
+//! Records written to dictionaries are prefixed with a one-byte layout
+//! version. Adding a field means bumping `VERSION` and teaching `decode` the
+//! old layout, so existing entries keep reading without a migration pass.
+//!
+//! An envelope is stored as a `List<U8>`, so casper-client, RPC
+//! `query_global_state` and explorers read it as plain bytes:
+//!
+//! ```text
+//! u32 LE length | u8 version | record fields (bytesrepr, per version)
+//! ```
+//!
+//! The length covers the version byte and the fields, which follow in the
+//! order the record's `Versioned::encode` writes them.
+
+use alloc::{boxed::Box, vec::Vec};
+use casper_types::{
+    bytesrepr::{self, FromBytes, ToBytes, U32_SERIALIZED_LENGTH, U8_SERIALIZED_LENGTH},
+    CLType, CLTyped,
+};
+
+/// A record with a versioned byte layout
+pub trait Versioned: Sized {
+    /// Layout version written by this build
+    const VERSION: u8;
+
+    /// Serialized length of the current layout, without the version byte
+    fn encoded_length(&self) -> usize;
+
+    /// Write the current layout, without the version byte
+    fn encode(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error>;
+
+    /// Read any layout version this build understands
+    fn decode(version: u8, bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error>;
+}
+
+/// Storage wrapper writing `T` behind its layout version
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Envelope<T>(pub T);
+
+impl<T> Envelope<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: Versioned> Envelope<T> {
+    /// Length of the `List<U8>` payload: version byte plus fields
+    fn payload_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH + self.0.encoded_length()
+    }
+}
+
+impl<T: Versioned> CLTyped for Envelope<T> {
+    fn cl_type() -> CLType {
+        CLType::List(Box::new(CLType::U8))
+    }
+}
+
+impl<T: Versioned> ToBytes for Envelope<T> {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut buffer)?;
+        Ok(buffer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        U32_SERIALIZED_LENGTH + self.payload_length()
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        let len = u32::try_from(self.payload_length())
+            .map_err(|_| bytesrepr::Error::NotRepresentable)?;
+        len.write_bytes(writer)?;
+        writer.push(T::VERSION);
+        self.0.encode(writer)
+    }
+}
+
+impl<T: Versioned> FromBytes for Envelope<T> {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (len, rem) = u32::from_bytes(bytes)?;
+        let len = len as usize;
+        if rem.len() < len {
+            return Err(bytesrepr::Error::EarlyEndOfStream);
+        }
+        let (payload, rem) = rem.split_at(len);
+
+        let (version, fields) = u8::from_bytes(payload)?;
+        if version == 0 || version > T::VERSION {
+            return Err(bytesrepr::Error::Formatting);
+        }
+        let (value, leftover) = T::decode(version, fields)?;
+        if !leftover.is_empty() {
+            return Err(bytesrepr::Error::LeftOverBytes);
+        }
+        Ok((Envelope(value), rem))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::bytesrepr::Bytes;
+
+    use super::*;
+
+    /// Version 1 held only `a`; version 2 added `b`
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    struct Record {
+        a: u64,
+        b: u32,
+    }
+
+    impl Versioned for Record {
+        const VERSION: u8 = 2;
+
+        fn encoded_length(&self) -> usize {
+            self.a.serialized_length() + self.b.serialized_length()
+        }
+
+        fn encode(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+            self.a.write_bytes(writer)?;
+            self.b.write_bytes(writer)
+        }
+
+        fn decode(version: u8, bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+            let (a, rem) = u64::from_bytes(bytes)?;
+            let (b, rem) = match version {
+                1 => (0, rem),
+                _ => u32::from_bytes(rem)?,
+            };
+            Ok((Record { a, b }, rem))
+        }
+    }
+
+    impl Record {
+        fn to_fields(&self) -> Vec<u8> {
+            let mut fields = Vec::new();
+            self.encode(&mut fields).unwrap();
+            fields
+        }
+    }
+
+    fn record() -> Envelope<Record> {
+        Envelope(Record { a: 7, b: 9 })
+    }
+
+    /// Hand-built envelope: length prefix, version, then `body`
+    fn raw(version: u8, body: &[u8]) -> Vec<u8> {
+        let mut bytes = (body.len() as u32 + 1).to_le_bytes().to_vec();
+        bytes.push(version);
+        bytes.extend_from_slice(body);
+        bytes
+    }
+
+    #[test]
+    fn round_trips_current_version() {
+        let bytes = record().to_bytes().unwrap();
+        assert_eq!(bytes.len(), record().serialized_length());
+        assert_eq!(bytesrepr::deserialize::<Envelope<Record>>(bytes).unwrap(), record());
+    }
+
+    #[test]
+    fn reads_as_plain_bytes() {
+        assert_eq!(Envelope::<Record>::cl_type(), Bytes::cl_type());
+        let bytes = record().to_bytes().unwrap();
+        let payload: Bytes = bytesrepr::deserialize(bytes).unwrap();
+        assert_eq!(payload[0], Record::VERSION);
+        assert_eq!(&payload[1..9], &7u64.to_le_bytes());
+        assert_eq!(&payload[9..], &9u32.to_le_bytes());
+    }
+
+    #[test]
+    fn decodes_older_versions() {
+        let bytes = raw(1, &7u64.to_le_bytes());
+        let decoded: Envelope<Record> = bytesrepr::deserialize(bytes).unwrap();
+        assert_eq!(decoded.into_inner(), Record { a: 7, b: 0 });
+    }
+
+    #[test]
+    fn rejects_unknown_versions() {
+        let body = record().0.to_fields();
+        for version in [0, Record::VERSION + 1] {
+            let result = bytesrepr::deserialize::<Envelope<Record>>(raw(version, &body));
+            assert_eq!(result, Err(bytesrepr::Error::Formatting));
+        }
+    }
+
+    #[test]
+    fn rejects_bytes_left_inside_the_payload() {
+        let mut body = record().0.to_fields();
+        body.push(0);
+        let result = bytesrepr::deserialize::<Envelope<Record>>(raw(2, &body));
+        assert_eq!(result, Err(bytesrepr::Error::LeftOverBytes));
+    }
+
+    #[test]
+    fn stops_at_the_payload_length() {
+        let mut bytes = record().to_bytes().unwrap();
+        bytes.push(0xff);
+        let (decoded, rem) = Envelope::<Record>::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded, record());
+        assert_eq!(rem, &[0xff]);
+    }
+
+    #[test]
+    fn rejects_truncated_payloads() {
+        let mut bytes = record().to_bytes().unwrap();
+        bytes.pop();
+        let result = Envelope::<Record>::from_bytes(&bytes);
+        assert_eq!(result.err(), Some(bytesrepr::Error::EarlyEndOfStream));
+    }
+}
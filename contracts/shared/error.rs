@@ -0,0 +1,372 @@
+//! Contract error codes
+//!
+//! Every revert raised by the bridge is `ApiError::User(code)` with a code
+//! from this table. Codes are stable: never renumber, only append. Client
+//! tooling decodes failed deploys with the same table, and `abi_gen`
+//! publishes it alongside the entry points.
+
+use casper_types::ApiError;
+
+/// Offset casper-types adds to user error codes in their `u32` form
+const USER_ERROR_OFFSET: u32 = 65_536;
+
+#[repr(u16)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// A named key the contract expects is missing
+    MissingKey = 1,
+    /// A named key holds something other than a URef
+    InvalidKeyType = 2,
+    /// A named key or dictionary entry has no value
+    MissingValue = 3,
+    /// A stored value failed to deserialize
+    Serialization = 4,
+    /// Caller lacks the role required for this entry point
+    Unauthorized = 5,
+    /// An argument is out of range or malformed
+    InvalidArgument = 6,
+    /// Account is blocked by address screening
+    Screened = 7,
+    /// A forced withdrawal has waited longer than the inclusion window
+    ForcedInclusionOverdue = 8,
+    /// Declared forced-queue range does not start at the queue head
+    InvalidForcedRange = 9,
+    /// No deposit address registered under this ID
+    UnknownDepositAddress = 10,
+    /// Deposit address purse is empty
+    NothingToSweep = 11,
+    /// L2 address already has a different public key registered
+    L2KeyMismatch = 12,
+    /// Session argument `contract_hash` not supplied
+    MissingContractHash = 13,
+    /// Session argument `contract_hash` is not a contract hash key
+    InvalidContractHash = 14,
+    /// Argument `amount` not supplied
+    MissingAmount = 15,
+    /// Argument `amount` is not a positive U512
+    InvalidAmount = 16,
+    /// Argument `purse` not supplied
+    MissingPurse = 17,
+    /// Argument `purse` is not a URef
+    InvalidPurse = 18,
+    /// Argument `l2_address` not supplied
+    MissingL2Address = 19,
+    /// Argument `l2_address` is not a non-empty string
+    InvalidL2Address = 20,
+    /// Argument `l2_public_key` not supplied
+    MissingL2PublicKey = 21,
+    /// Argument `l2_public_key` is not a non-empty byte list
+    InvalidL2PublicKey = 22,
+    /// Source purse balance is below the requested amount
+    InsufficientBalance = 23,
+    /// Session argument `contract_package_hash` not supplied
+    MissingContractPackageHash = 24,
+    /// Session argument `contract_package_hash` is not a package hash key
+    InvalidContractPackageHash = 25,
+    /// Session argument `contract_version` is not a u32
+    InvalidContractVersion = 26,
+    /// Installation is incomplete or inconsistent
+    SelfCheckFailed = 27,
+    /// `init` has already run
+    AlreadyInitialized = 28,
+    /// Withdrawal proof is malformed or does not reach the state root
+    InvalidMerkleProof = 29,
+    /// Verifying key does not decode or has the wrong number of public inputs
+    InvalidVerifyingKey = 30,
+    /// Batch proof is malformed or fails the pairing check
+    InvalidProof = 31,
+    /// Proof argument has the wrong length for its kind
+    InvalidProofLength = 32,
+    /// Bridge is paused by the admin
+    Paused = 33,
+    /// This withdrawal has already been paid out
+    WithdrawalAlreadyClaimed = 34,
+    /// Moving deposited funds into a bridge purse failed
+    DepositTransferFailed = 35,
+    /// Paying a withdrawal out of the contract purse failed
+    WithdrawalTransferFailed = 36,
+    /// The host rejected an event message
+    MessageEmitFailed = 37,
+    /// Deposit would push a canary install over its value cap
+    CanaryCapExceeded = 38,
+    /// Canary install has expired and only accepts withdrawals
+    CanaryExpired = 39,
+    /// No pending withdrawal under this index
+    UnknownWithdrawal = 40,
+    /// Withdrawal is still inside its challenge window
+    ChallengeWindowOpen = 41,
+    /// Write past the end of an indexed dictionary
+    IndexOutOfBounds = 42,
+    /// Caller did not register this L2 address's key
+    NotL2Owner = 43,
+    /// This L2 address already has a forced withdrawal waiting for a batch
+    ForcedWithdrawalPending = 44,
+    /// The withdrawal's state root has been disputed
+    RootDisputed = 45,
+    /// Only withdrawals proven against a disputed root can be cancelled
+    RootNotDisputed = 46,
+    /// The withdrawal is no longer pending
+    WithdrawalNotPending = 47,
+    /// Withdrawal expiry is turned off
+    WithdrawalExpiryDisabled = 48,
+    /// The withdrawal has not been unclaimed long enough to expire
+    ExpiryWindowOpen = 49,
+    /// Only expired withdrawals can be reactivated
+    WithdrawalNotExpired = 50,
+    /// The expired withdrawal's grace window has passed
+    GraceWindowClosed = 51,
+    /// Only the bridge contract may mint or burn wCSPR
+    NotBridge = 52,
+    /// The wCSPR balance is too low
+    InsufficientWcspr = 53,
+    /// The wCSPR allowance is too low
+    InsufficientAllowance = 54,
+    /// No wCSPR token has been configured
+    WcsprTokenNotSet = 55,
+    /// Only the withdrawal's recipient account can wrap or assign it
+    NotWithdrawalRecipient = 56,
+    /// The assignment signature does not verify against the recipient's key
+    InvalidAssignment = 57,
+    /// The pool has no fronted withdrawal at this index
+    NotFronted = 58,
+    /// The provider holds fewer pool shares than requested
+    InsufficientShares = 59,
+    /// The pool purse cannot cover the payout
+    InsufficientLiquidity = 60,
+    /// Retired with the configurable hash backend; no longer returned
+    UnsupportedHashBackend = 61,
+    /// Circuit identifier does not match the verifying key
+    CircuitIdMismatch = 62,
+    /// No verification routine for this proof system in this build
+    UnsupportedProofSystem = 63,
+    /// The batch's proof system is not currently accepted
+    ProofSystemNotAccepted = 64,
+    /// No proving bounty is posted for this batch
+    NoBounty = 65,
+    /// Redemption is larger than what is left of the wrapped withdrawal
+    RedemptionExceedsWrapped = 66,
+    /// The bounty claim was not committed in an earlier block
+    NoClaimCommitment = 67,
+}
+
+impl Error {
+    /// All known errors, in code order
+    pub const ALL: &'static [Error] = &[
+        Error::MissingKey,
+        Error::InvalidKeyType,
+        Error::MissingValue,
+        Error::Serialization,
+        Error::Unauthorized,
+        Error::InvalidArgument,
+        Error::Screened,
+        Error::ForcedInclusionOverdue,
+        Error::InvalidForcedRange,
+        Error::UnknownDepositAddress,
+        Error::NothingToSweep,
+        Error::L2KeyMismatch,
+        Error::MissingContractHash,
+        Error::InvalidContractHash,
+        Error::MissingAmount,
+        Error::InvalidAmount,
+        Error::MissingPurse,
+        Error::InvalidPurse,
+        Error::MissingL2Address,
+        Error::InvalidL2Address,
+        Error::MissingL2PublicKey,
+        Error::InvalidL2PublicKey,
+        Error::InsufficientBalance,
+        Error::MissingContractPackageHash,
+        Error::InvalidContractPackageHash,
+        Error::InvalidContractVersion,
+        Error::SelfCheckFailed,
+        Error::AlreadyInitialized,
+        Error::InvalidMerkleProof,
+        Error::InvalidVerifyingKey,
+        Error::InvalidProof,
+        Error::InvalidProofLength,
+        Error::Paused,
+        Error::WithdrawalAlreadyClaimed,
+        Error::DepositTransferFailed,
+        Error::WithdrawalTransferFailed,
+        Error::MessageEmitFailed,
+        Error::CanaryCapExceeded,
+        Error::CanaryExpired,
+        Error::UnknownWithdrawal,
+        Error::ChallengeWindowOpen,
+        Error::IndexOutOfBounds,
+        Error::NotL2Owner,
+        Error::ForcedWithdrawalPending,
+        Error::RootDisputed,
+        Error::RootNotDisputed,
+        Error::WithdrawalNotPending,
+        Error::WithdrawalExpiryDisabled,
+        Error::ExpiryWindowOpen,
+        Error::WithdrawalNotExpired,
+        Error::GraceWindowClosed,
+        Error::NotBridge,
+        Error::InsufficientWcspr,
+        Error::InsufficientAllowance,
+        Error::WcsprTokenNotSet,
+        Error::NotWithdrawalRecipient,
+        Error::InvalidAssignment,
+        Error::NotFronted,
+        Error::InsufficientShares,
+        Error::InsufficientLiquidity,
+        Error::UnsupportedHashBackend,
+        Error::CircuitIdMismatch,
+        Error::UnsupportedProofSystem,
+        Error::ProofSystemNotAccepted,
+        Error::NoBounty,
+        Error::RedemptionExceedsWrapped,
+        Error::NoClaimCommitment,
+    ];
+
+    pub fn code(self) -> u16 {
+        self as u16
+    }
+
+    pub fn from_code(code: u16) -> Option<Self> {
+        Self::ALL.iter().copied().find(|e| e.code() == code)
+    }
+
+    /// Look up an error from the `u32` an `ApiError` serializes to, as found
+    /// in failed deploy execution results
+    pub fn from_api_error_code(code: u32) -> Option<Self> {
+        let user = code.checked_sub(USER_ERROR_OFFSET)?;
+        u16::try_from(user).ok().and_then(Self::from_code)
+    }
+
+    /// Human-readable explanation for client tooling
+    pub fn message(self) -> &'static str {
+        match self {
+            Error::MissingKey => "contract is not initialized: a required named key is missing",
+            Error::InvalidKeyType => "contract storage is corrupt: named key is not a URef",
+            Error::MissingValue => "contract storage is corrupt: stored value is missing",
+            Error::Serialization => "stored value could not be decoded",
+            Error::Unauthorized => "caller is not authorized for this action",
+            Error::InvalidArgument => "an argument is out of range or malformed",
+            Error::Screened => "account is not permitted to use this bridge",
+            Error::ForcedInclusionOverdue => {
+                "batch rejected: a forced withdrawal is past its inclusion deadline"
+            }
+            Error::InvalidForcedRange => {
+                "batch rejected: forced queue range must start at the queue head"
+            }
+            Error::UnknownDepositAddress => "no deposit address is registered under this ID",
+            Error::NothingToSweep => "deposit address has no funds to sweep",
+            Error::L2KeyMismatch => "L2 address is already registered to a different key",
+            Error::MissingContractHash => "missing argument: contract_hash",
+            Error::InvalidContractHash => "contract_hash must be a hash-... key",
+            Error::MissingAmount => "missing argument: amount",
+            Error::InvalidAmount => "amount must be a positive U512",
+            Error::MissingPurse => "missing argument: purse",
+            Error::InvalidPurse => "purse must be a URef",
+            Error::MissingL2Address => "missing argument: l2_address",
+            Error::InvalidL2Address => "l2_address must be a non-empty string",
+            Error::MissingL2PublicKey => "missing argument: l2_public_key",
+            Error::InvalidL2PublicKey => "l2_public_key must be a non-empty byte list",
+            Error::InsufficientBalance => "source purse balance is below the requested amount",
+            Error::MissingContractPackageHash => "missing argument: contract_package_hash",
+            Error::InvalidContractPackageHash => "contract_package_hash must be a hash-... key",
+            Error::InvalidContractVersion => "contract_version must be a u32",
+            Error::SelfCheckFailed => "contract installation is incomplete or inconsistent",
+            Error::AlreadyInitialized => "contract is already initialized",
+            Error::InvalidMerkleProof => "withdrawal is not included in the current state root",
+            Error::InvalidVerifyingKey => "verifying_key is not a valid BN254 Groth16 key",
+            Error::InvalidProof => "batch proof is invalid",
+            Error::InvalidProofLength => "proof has the wrong length",
+            Error::Paused => "bridge is paused",
+            Error::WithdrawalAlreadyClaimed => "withdrawal has already been claimed",
+            Error::DepositTransferFailed => {
+                "deposit transfer failed: check the source purse balance and access rights"
+            }
+            Error::WithdrawalTransferFailed => "withdrawal payout from the contract purse failed",
+            Error::MessageEmitFailed => "event message was rejected by the host",
+            Error::CanaryCapExceeded => "deposit would exceed the canary value cap",
+            Error::CanaryExpired => "canary deployment has expired: withdrawals only",
+            Error::UnknownWithdrawal => "no pending withdrawal under this index",
+            Error::ChallengeWindowOpen => "withdrawal is still inside its challenge window",
+            Error::IndexOutOfBounds => "contract storage is corrupt: index past the end of a list",
+            Error::NotL2Owner => "caller is not the account that registered this L2 address",
+            Error::ForcedWithdrawalPending => {
+                "a forced withdrawal for this L2 address is already queued"
+            }
+            Error::RootDisputed => "withdrawal was proven against a disputed state root",
+            Error::RootNotDisputed => "withdrawal's state root has not been disputed",
+            Error::WithdrawalNotPending => "withdrawal is no longer pending",
+            Error::WithdrawalExpiryDisabled => "withdrawal expiry is disabled",
+            Error::ExpiryWindowOpen => "withdrawal has not reached its expiry height",
+            Error::WithdrawalNotExpired => "withdrawal has not expired",
+            Error::GraceWindowClosed => "expired withdrawal's grace window has closed",
+            Error::NotBridge => "only the bridge contract may mint or burn wCSPR",
+            Error::InsufficientWcspr => "wCSPR balance is too low",
+            Error::InsufficientAllowance => "wCSPR allowance is too low",
+            Error::WcsprTokenNotSet => "no wCSPR token is configured",
+            Error::NotWithdrawalRecipient => {
+                "only the withdrawal's recipient account can wrap or assign it"
+            }
+            Error::InvalidAssignment => "assignment signature does not verify",
+            Error::NotFronted => "pool has not fronted this withdrawal",
+            Error::InsufficientShares => "provider holds too few pool shares",
+            Error::InsufficientLiquidity => "pool purse cannot cover the payout",
+            Error::UnsupportedHashBackend => "hash backend is not supported by this contract",
+            Error::CircuitIdMismatch => "circuit identifier does not match the verifying key",
+            Error::UnsupportedProofSystem => "proof system is not supported by this contract",
+            Error::ProofSystemNotAccepted => "proof system is not accepted for new batches",
+            Error::NoBounty => "no proving bounty is posted for this batch",
+            Error::RedemptionExceedsWrapped => {
+                "amount exceeds the wCSPR left to redeem against this withdrawal"
+            }
+            Error::NoClaimCommitment => "bounty claim was not committed in an earlier block",
+        }
+    }
+}
+
+impl From<Error> for ApiError {
+    fn from(error: Error) -> Self {
+        ApiError::User(error.code())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::collections::BTreeSet;
+
+    use super::*;
+
+    #[test]
+    fn codes_are_dense_in_declaration_order() {
+        // Append-only numbering from 1 means a variant missing from `ALL`
+        // shows up as a gap
+        for (i, error) in Error::ALL.iter().enumerate() {
+            assert_eq!(usize::from(error.code()), i + 1, "{:?}", error);
+        }
+    }
+
+    #[test]
+    fn every_code_round_trips() {
+        for &error in Error::ALL {
+            assert_eq!(Error::from_code(error.code()), Some(error));
+            let api_error = ApiError::from(error);
+            assert_eq!(api_error, ApiError::User(error.code()));
+            assert_eq!(Error::from_api_error_code(u32::from(api_error)), Some(error));
+        }
+    }
+
+    #[test]
+    fn unknown_codes_do_not_decode() {
+        let next = Error::ALL.len() as u16 + 1;
+        assert_eq!(Error::from_code(0), None);
+        assert_eq!(Error::from_code(next), None);
+        assert_eq!(Error::from_api_error_code(0), None);
+        assert_eq!(Error::from_api_error_code(u32::from(ApiError::MissingArgument)), None);
+        assert_eq!(Error::from_api_error_code(USER_ERROR_OFFSET + u32::from(next)), None);
+    }
+
+    #[test]
+    fn messages_are_distinct() {
+        let messages: BTreeSet<_> = Error::ALL.iter().map(|e| e.message()).collect();
+        assert_eq!(messages.len(), Error::ALL.len());
+        assert!(messages.iter().all(|m| !m.is_empty()));
+    }
+}
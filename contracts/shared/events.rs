@@ -0,0 +1,445 @@
+//! Message topics and payload encodings
+//!
+//! Payloads are bytesrepr-encoded structs published as
+//! `MessagePayload::Bytes`. Each event is declared once in the `events!`
+//! table below, which generates the struct, its topic and its codec, so
+//! field order in the table is the wire format. The contract registers every
+//! topic in `TOPICS` at install and only encodes; indexers decode what they
+//! receive over SSE with `ContractEvent::decode`.
+
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+
+use casper_types::{
+    account::AccountHash,
+    bytesrepr::{self, Bytes, FromBytes, ToBytes},
+    contract_messages::MessagePayload,
+    Key, U512,
+};
+
+use crate::types::AuditAction;
+
+pub const TOPIC_DEPOSIT: &str = "deposits";
+pub const TOPIC_WITHDRAWAL: &str = "withdrawals";
+pub const TOPIC_BATCH_SUBMITTED: &str = "batches";
+pub const TOPIC_PAUSED: &str = "paused";
+pub const TOPIC_CONFIG_CHANGED: &str = "config";
+pub const TOPIC_AUDIT: &str = "audit";
+pub const TOPIC_FORCED_WITHDRAWAL: &str = "forced_withdrawals";
+pub const TOPIC_KEYED_DEPOSIT: &str = "keyed_deposits";
+pub const TOPIC_ROOT_DISPUTED: &str = "root_disputes";
+pub const TOPIC_WITHDRAWAL_CANCELLED: &str = "withdrawal_cancellations";
+pub const TOPIC_WITHDRAWAL_EXPIRED: &str = "withdrawal_expiries";
+pub const TOPIC_WITHDRAWAL_REACTIVATED: &str = "withdrawal_reactivations";
+pub const TOPIC_WITHDRAWAL_WRAPPED: &str = "withdrawal_wraps";
+pub const TOPIC_WITHDRAWAL_ASSIGNED: &str = "withdrawal_assignments";
+pub const TOPIC_BOUNTY_POSTED: &str = "proving_bounties";
+pub const TOPIC_BOUNTY_CLAIMED: &str = "bounty_claims";
+
+/// A payload published under a fixed message topic
+pub trait Event: ToBytes + FromBytes {
+    const TOPIC: &'static str;
+
+    fn to_payload(&self) -> Result<MessagePayload, bytesrepr::Error> {
+        Ok(MessagePayload::Bytes(Bytes::from(self.to_bytes()?)))
+    }
+
+    /// Decode a payload received under `TOPIC`
+    fn from_payload(payload: &MessagePayload) -> Result<Self, DecodeError> {
+        match payload {
+            MessagePayload::Bytes(bytes) => {
+                bytesrepr::deserialize_from_slice(bytes).map_err(DecodeError::Payload)
+            }
+            MessagePayload::String(_) => Err(DecodeError::NotBytes),
+        }
+    }
+}
+
+/// Why a received message could not be decoded
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The topic is not one the contract emits
+    UnknownTopic,
+    /// The payload is a string message, not bytes
+    NotBytes,
+    /// The bytes do not decode as the topic's event
+    Payload(bytesrepr::Error),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DecodeError::UnknownTopic => f.write_str("unknown message topic"),
+            DecodeError::NotBytes => f.write_str("message payload is not bytes"),
+            DecodeError::Payload(error) => write!(f, "malformed event payload: {}", error),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecodeError {}
+
+/// Declares each event's struct, topic and codec, plus `TOPICS` and
+/// `ContractEvent` covering all of them
+macro_rules! events {
+    ($(
+        $(#[$meta:meta])*
+        $name:ident => $topic:ident {
+            $($(#[$field_meta:meta])* $field:ident: $ty:ty,)+
+        }
+    )+) => {
+        $(
+            $(#[$meta])*
+            #[derive(Clone, Debug, PartialEq, Eq)]
+            pub struct $name {
+                $($(#[$field_meta])* pub $field: $ty,)+
+            }
+
+            impl Event for $name {
+                const TOPIC: &'static str = $topic;
+            }
+
+            impl ToBytes for $name {
+                fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+                    let mut buffer = bytesrepr::allocate_buffer(self)?;
+                    self.write_bytes(&mut buffer)?;
+                    Ok(buffer)
+                }
+
+                fn serialized_length(&self) -> usize {
+                    0 $(+ self.$field.serialized_length())+
+                }
+
+                fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+                    $(self.$field.write_bytes(writer)?;)+
+                    Ok(())
+                }
+            }
+
+            impl FromBytes for $name {
+                fn from_bytes(rem: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+                    $(let ($field, rem) = <$ty>::from_bytes(rem)?;)+
+                    Ok(($name { $($field,)+ }, rem))
+                }
+            }
+        )+
+
+        /// Topics registered when the contract is installed
+        pub const TOPICS: &[&str] = &[$($topic,)+];
+
+        /// Any event the contract emits
+        #[derive(Clone, Debug, PartialEq, Eq)]
+        pub enum ContractEvent {
+            $($name($name),)+
+        }
+
+        impl ContractEvent {
+            /// Decode a message received under `topic`
+            pub fn decode(topic: &str, payload: &MessagePayload) -> Result<Self, DecodeError> {
+                $(
+                    if topic == $topic {
+                        return $name::from_payload(payload).map(ContractEvent::$name);
+                    }
+                )+
+                Err(DecodeError::UnknownTopic)
+            }
+
+            pub fn topic(&self) -> &'static str {
+                match self {
+                    $(ContractEvent::$name(_) => $topic,)+
+                }
+            }
+        }
+    };
+}
+
+events! {
+    /// CSPR locked in the bridge for an L2 account
+    Deposit => TOPIC_DEPOSIT {
+        depositor: AccountHash,
+        amount: U512,
+        l2_address: String,
+        deposit_index: u64,
+    }
+
+    /// CSPR released from the bridge back to L1
+    Withdrawal => TOPIC_WITHDRAWAL {
+        recipient: Key,
+        amount: U512,
+    }
+
+    /// A new state root accepted
+    BatchSubmitted => TOPIC_BATCH_SUBMITTED {
+        batch_index: u64,
+        root: U512,
+    }
+
+    /// Bridge paused or unpaused
+    Paused => TOPIC_PAUSED {
+        paused: bool,
+        by: AccountHash,
+    }
+
+    /// A configuration value changed; `key` names the setting
+    ConfigChanged => TOPIC_CONFIG_CHANGED {
+        key: String,
+        by: AccountHash,
+    }
+
+    /// A privileged call appended to the audit log at `index`
+    PrivilegedAction => TOPIC_AUDIT {
+        index: u64,
+        action: AuditAction,
+        caller: AccountHash,
+        args_hash: [u8; 32],
+        blocktime: u64,
+    }
+
+    /// A forced L2 withdrawal queued at `index`
+    ForcedWithdrawalRequested => TOPIC_FORCED_WITHDRAWAL {
+        index: u64,
+        requester: AccountHash,
+        l2_address: String,
+        amount: U512,
+    }
+
+    /// A deposit that also registers the L2 account's public key; the
+    /// sequencer creates the account and credits it from this single event
+    KeyedDeposit => TOPIC_KEYED_DEPOSIT {
+        depositor: AccountHash,
+        amount: U512,
+        l2_address: String,
+        l2_public_key: Bytes,
+    }
+
+    /// A state root flagged as bad; withdrawals proven against it cannot
+    /// be paid out
+    RootDisputed => TOPIC_ROOT_DISPUTED {
+        root: U512,
+        by: AccountHash,
+    }
+
+    /// A pending withdrawal against a disputed root dropped from the queue
+    WithdrawalCancelled => TOPIC_WITHDRAWAL_CANCELLED {
+        index: u64,
+        recipient: Key,
+        amount: U512,
+    }
+
+    /// An unclaimed withdrawal expired; `amount` moved to the insurance fund
+    WithdrawalExpired => TOPIC_WITHDRAWAL_EXPIRED {
+        index: u64,
+        recipient: Key,
+        amount: U512,
+    }
+
+    /// An expired withdrawal restored from the insurance fund within its
+    /// grace window; it can be finalized again
+    WithdrawalReactivated => TOPIC_WITHDRAWAL_REACTIVATED {
+        index: u64,
+        recipient: Key,
+        amount: U512,
+    }
+
+    /// `amount` wCSPR minted to `owner` against its pending withdrawal
+    WithdrawalWrapped => TOPIC_WITHDRAWAL_WRAPPED {
+        index: u64,
+        owner: Key,
+        amount: U512,
+    }
+
+    /// A pending withdrawal signed over from `from` to `to`
+    WithdrawalAssigned => TOPIC_WITHDRAWAL_ASSIGNED {
+        index: u64,
+        from: AccountHash,
+        to: Key,
+        amount: U512,
+    }
+
+    /// A proving bounty for `batch_hash` raised to `reward`
+    BountyPosted => TOPIC_BOUNTY_POSTED {
+        batch_hash: [u8; 32],
+        reward: U512,
+    }
+
+    /// `prover` paid `reward` for `proof` of the batch `batch_hash`
+    BountyClaimed => TOPIC_BOUNTY_CLAIMED {
+        batch_hash: [u8; 32],
+        prover: Key,
+        reward: U512,
+        proof: Bytes,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{collections::BTreeSet, vec};
+
+    use super::*;
+
+    fn account(byte: u8) -> AccountHash {
+        AccountHash::new([byte; 32])
+    }
+
+    fn samples() -> Vec<ContractEvent> {
+        vec![
+            ContractEvent::Deposit(Deposit {
+                depositor: account(1),
+                amount: U512::from(5_000_000_000u64),
+                l2_address: "l2-alice".into(),
+                deposit_index: 7,
+            }),
+            ContractEvent::Withdrawal(Withdrawal {
+                recipient: Key::Account(account(2)),
+                amount: U512::from(42u64),
+            }),
+            ContractEvent::BatchSubmitted(BatchSubmitted {
+                batch_index: 3,
+                root: U512::from(100u64),
+            }),
+            ContractEvent::Paused(Paused {
+                paused: true,
+                by: account(3),
+            }),
+            ContractEvent::ConfigChanged(ConfigChanged {
+                key: "sequencer".into(),
+                by: account(4),
+            }),
+            ContractEvent::PrivilegedAction(PrivilegedAction {
+                index: 0,
+                action: AuditAction::SetPaused,
+                caller: account(5),
+                args_hash: [9; 32],
+                blocktime: 1_700_000_000_000,
+            }),
+            ContractEvent::ForcedWithdrawalRequested(ForcedWithdrawalRequested {
+                index: 1,
+                requester: account(6),
+                l2_address: "l2-bob".into(),
+                amount: U512::one(),
+            }),
+            ContractEvent::KeyedDeposit(KeyedDeposit {
+                depositor: account(7),
+                amount: U512::from(10u64),
+                l2_address: "l2-carol".into(),
+                l2_public_key: Bytes::from(vec![1, 2, 3]),
+            }),
+            ContractEvent::RootDisputed(RootDisputed {
+                root: U512::from(77u64),
+                by: account(8),
+            }),
+            ContractEvent::WithdrawalCancelled(WithdrawalCancelled {
+                index: 2,
+                recipient: Key::Account(account(9)),
+                amount: U512::from(3u64),
+            }),
+            ContractEvent::WithdrawalExpired(WithdrawalExpired {
+                index: 4,
+                recipient: Key::Account(account(10)),
+                amount: U512::from(11u64),
+            }),
+            ContractEvent::WithdrawalReactivated(WithdrawalReactivated {
+                index: 4,
+                recipient: Key::Account(account(10)),
+                amount: U512::from(11u64),
+            }),
+            ContractEvent::WithdrawalWrapped(WithdrawalWrapped {
+                index: 5,
+                owner: Key::Account(account(11)),
+                amount: U512::from(12u64),
+            }),
+            ContractEvent::WithdrawalAssigned(WithdrawalAssigned {
+                index: 6,
+                from: account(12),
+                to: Key::Hash([13; 32]),
+                amount: U512::from(14u64),
+            }),
+            ContractEvent::BountyPosted(BountyPosted {
+                batch_hash: [15; 32],
+                reward: U512::from(16u64),
+            }),
+            ContractEvent::BountyClaimed(BountyClaimed {
+                batch_hash: [17; 32],
+                prover: Key::Account(account(18)),
+                reward: U512::from(19u64),
+                proof: Bytes::from(vec![20, 21]),
+            }),
+        ]
+    }
+
+    fn payload(event: &ContractEvent) -> MessagePayload {
+        match event {
+            ContractEvent::Deposit(e) => e.to_payload(),
+            ContractEvent::Withdrawal(e) => e.to_payload(),
+            ContractEvent::BatchSubmitted(e) => e.to_payload(),
+            ContractEvent::Paused(e) => e.to_payload(),
+            ContractEvent::ConfigChanged(e) => e.to_payload(),
+            ContractEvent::PrivilegedAction(e) => e.to_payload(),
+            ContractEvent::ForcedWithdrawalRequested(e) => e.to_payload(),
+            ContractEvent::KeyedDeposit(e) => e.to_payload(),
+            ContractEvent::RootDisputed(e) => e.to_payload(),
+            ContractEvent::WithdrawalCancelled(e) => e.to_payload(),
+            ContractEvent::WithdrawalExpired(e) => e.to_payload(),
+            ContractEvent::WithdrawalReactivated(e) => e.to_payload(),
+            ContractEvent::WithdrawalWrapped(e) => e.to_payload(),
+            ContractEvent::WithdrawalAssigned(e) => e.to_payload(),
+            ContractEvent::BountyPosted(e) => e.to_payload(),
+            ContractEvent::BountyClaimed(e) => e.to_payload(),
+        }
+        .unwrap()
+    }
+
+    #[test]
+    fn every_event_round_trips_through_its_topic() {
+        let samples = samples();
+        assert_eq!(samples.len(), TOPICS.len());
+        for event in samples {
+            let decoded = ContractEvent::decode(event.topic(), &payload(&event)).unwrap();
+            assert_eq!(decoded, event);
+        }
+    }
+
+    #[test]
+    fn topics_are_distinct() {
+        let topics: BTreeSet<_> = TOPICS.iter().collect();
+        assert_eq!(topics.len(), TOPICS.len());
+    }
+
+    #[test]
+    fn fields_are_encoded_in_declaration_order() {
+        let event = BatchSubmitted {
+            batch_index: 3,
+            root: U512::from(0x0102u64),
+        };
+        let mut expected = 3u64.to_le_bytes().to_vec();
+        // U512: length byte, then little-endian magnitude
+        expected.extend_from_slice(&[2, 0x02, 0x01]);
+        assert_eq!(event.to_bytes().unwrap(), expected);
+        assert_eq!(event.serialized_length(), expected.len());
+    }
+
+    #[test]
+    fn rejects_unknown_topics_and_bad_payloads() {
+        let bytes = payload(&samples()[1]);
+        assert_eq!(
+            ContractEvent::decode("nope", &bytes),
+            Err(DecodeError::UnknownTopic)
+        );
+        assert_eq!(
+            ContractEvent::decode(TOPIC_WITHDRAWAL, &MessagePayload::String("hi".into())),
+            Err(DecodeError::NotBytes)
+        );
+
+        let MessagePayload::Bytes(bytes) = bytes else {
+            unreachable!()
+        };
+        let mut longer = bytes.to_vec();
+        longer.push(0);
+        assert_eq!(
+            Withdrawal::from_payload(&MessagePayload::Bytes(longer.into())),
+            Err(DecodeError::Payload(bytesrepr::Error::LeftOverBytes))
+        );
+        let shorter = bytes[..bytes.len() - 1].to_vec();
+        assert!(Withdrawal::from_payload(&MessagePayload::Bytes(shorter.into())).is_err());
+    }
+}
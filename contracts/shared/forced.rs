@@ -0,0 +1,106 @@
+//! Entries of the forced-withdrawal queue
+//!
+//! The contract stores each request behind its layout version and extends
+//! the queue's accumulator with it, `acc[i + 1] = blake2b(acc[i] || entry)`.
+//! Provers compute the batch's accumulator inputs with the same `extend`.
+
+use alloc::{string::String, vec::Vec};
+use casper_types::{
+    account::AccountHash,
+    bytesrepr::{self, FromBytes, ToBytes},
+    U512,
+};
+
+use crate::{
+    envelope::{Envelope, Versioned},
+    hash,
+};
+
+/// A queued forced withdrawal: burn `amount` from `l2_address` and exit it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ForcedTx {
+    pub requester: AccountHash,
+    pub l2_address: String,
+    pub amount: U512,
+    /// Batch count when the request was queued
+    pub queued_at_batch: u64,
+}
+
+impl Versioned for ForcedTx {
+    const VERSION: u8 = 1;
+
+    fn encoded_length(&self) -> usize {
+        self.requester.serialized_length()
+            + self.l2_address.serialized_length()
+            + self.amount.serialized_length()
+            + self.queued_at_batch.serialized_length()
+    }
+
+    fn encode(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        self.requester.write_bytes(writer)?;
+        self.l2_address.write_bytes(writer)?;
+        self.amount.write_bytes(writer)?;
+        self.queued_at_batch.write_bytes(writer)
+    }
+
+    fn decode(_version: u8, bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (requester, rem) = AccountHash::from_bytes(bytes)?;
+        let (l2_address, rem) = String::from_bytes(rem)?;
+        let (amount, rem) = U512::from_bytes(rem)?;
+        let (queued_at_batch, rem) = u64::from_bytes(rem)?;
+        Ok((
+            ForcedTx {
+                requester,
+                l2_address,
+                amount,
+                queued_at_batch,
+            },
+            rem,
+        ))
+    }
+}
+
+/// `acc[i + 1]` from `acc[i]` and entry `i`
+pub fn extend(acc: [u8; 32], entry: &Envelope<ForcedTx>) -> Result<[u8; 32], bytesrepr::Error> {
+    let mut preimage = Vec::from(acc);
+    entry.write_bytes(&mut preimage)?;
+    Ok(hash::blake2b(preimage))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(l2_address: &str, amount: u64) -> Envelope<ForcedTx> {
+        Envelope(ForcedTx {
+            requester: AccountHash::new([1; 32]),
+            l2_address: String::from(l2_address),
+            amount: U512::from(amount),
+            queued_at_batch: 4,
+        })
+    }
+
+    #[test]
+    fn extend_hashes_previous_accumulator_then_entry() {
+        let entry = entry("l2-alice", 10);
+        let mut preimage = Vec::from([0u8; 32]);
+        preimage.extend(entry.to_bytes().unwrap());
+        assert_eq!(
+            extend([0; 32], &entry).unwrap(),
+            casper_types::Digest::hash(preimage).value()
+        );
+    }
+
+    #[test]
+    fn accumulator_commits_to_every_entry_and_order() {
+        let a = entry("l2-alice", 10);
+        let b = entry("l2-bob", 20);
+        let ab = extend(extend([0; 32], &a).unwrap(), &b).unwrap();
+        let ba = extend(extend([0; 32], &b).unwrap(), &a).unwrap();
+        assert_ne!(ab, ba);
+
+        let changed = entry("l2-bob", 21);
+        let a_changed = extend(extend([0; 32], &a).unwrap(), &changed).unwrap();
+        assert_ne!(ab, a_changed);
+    }
+}
@@ -0,0 +1,17 @@
+//! blake2b-256 for hashes the contract commits to
+//!
+//! On-chain this is the host's `blake2b`. Host builds, the unit tests and
+//! `accelerate-verify`, have no host to call, so they use casper-types'
+//! implementation of the same function.
+
+/// blake2b-256 of `data`
+#[cfg(target_arch = "wasm32")]
+pub fn blake2b<T: AsRef<[u8]>>(data: T) -> [u8; 32] {
+    casper_contract::contract_api::runtime::blake2b(data)
+}
+
+/// blake2b-256 of `data`
+#[cfg(not(target_arch = "wasm32"))]
+pub fn blake2b<T: AsRef<[u8]>>(data: T) -> [u8; 32] {
+    casper_types::Digest::hash(data).value()
+}
@@ -0,0 +1,25 @@
+//! Wire formats and pure checks shared between the contract and off-chain
+//! code
+//!
+//! The contract builds this crate `no_std` and only encodes. Indexers and
+//! other host tools enable `std` and decode the same types, so both sides
+//! agree on field order by construction instead of by hand-written offsets.
+//! The hashing, Merkle and proof checks the contract runs live here too, so
+//! `accelerate-verify` checks batches and withdrawals with the same code.
+
+#![cfg_attr(not(any(test, feature = "std")), no_std)]
+
+extern crate alloc;
+
+pub mod batches;
+pub mod ct;
+pub mod envelope;
+pub mod error;
+pub mod events;
+pub mod forced;
+pub mod groth16;
+pub mod hash;
+pub mod merkle;
+pub mod proof;
+pub mod public_inputs;
+pub mod types;
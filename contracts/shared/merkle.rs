@@ -0,0 +1,149 @@
+//! Withdrawal inclusion proofs against the state root
+//!
+//! Leaves and interior nodes are hashed under different prefixes so a node
+//! can never pass as a leaf. Each pair is hashed in sorted order, so a proof
+//! is just the sibling hashes from leaf to root, 32 bytes each, with no
+//! left/right flags.
+
+use alloc::vec::Vec;
+use casper_types::{
+    bytesrepr::{self, ToBytes},
+    Key, U512,
+};
+
+use crate::{ct, hash, proof::MerkleProof};
+
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+
+/// Leaf committing to one withdrawal: `blake2b(0x00 || recipient || amount || nonce)`
+pub fn withdrawal_leaf(
+    recipient: &Key,
+    amount: U512,
+    nonce: u64,
+) -> Result<[u8; 32], bytesrepr::Error> {
+    let mut preimage = Vec::with_capacity(
+        1 + recipient.serialized_length() + amount.serialized_length() + 8,
+    );
+    preimage.push(LEAF_PREFIX);
+    recipient.write_bytes(&mut preimage)?;
+    amount.write_bytes(&mut preimage)?;
+    nonce.write_bytes(&mut preimage)?;
+    Ok(hash::blake2b(preimage))
+}
+
+fn node(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (lo, hi) = if a <= b { (a, b) } else { (b, a) };
+    let mut preimage = [0u8; 65];
+    preimage[0] = NODE_PREFIX;
+    preimage[1..33].copy_from_slice(lo);
+    preimage[33..].copy_from_slice(hi);
+    hash::blake2b(preimage)
+}
+
+/// Root reached by walking the proof's siblings up from `leaf`
+pub fn compute_root(leaf: [u8; 32], proof: &MerkleProof) -> [u8; 32] {
+    proof
+        .siblings()
+        .fold(leaf, |acc, sibling| node(&acc, sibling))
+}
+
+/// The 32-byte hash held in a `U512` state root, big-endian
+///
+/// `None` if the root has bits set above 256, which no hash can produce.
+pub fn root_bytes(root: U512) -> Option<[u8; 32]> {
+    let mut buf = [0u8; 64];
+    root.to_big_endian(&mut buf);
+    let (high, low) = buf.split_at(32);
+    if !ct::is_zero(high) {
+        return None;
+    }
+    let mut out = [0u8; 32];
+    out.copy_from_slice(low);
+    Some(out)
+}
+
+/// Whether `proof` shows `leaf` is included under `root`
+pub fn verify(leaf: [u8; 32], proof: &MerkleProof, root: U512) -> bool {
+    match root_bytes(root) {
+        Some(expected) => ct::eq_32(&compute_root(leaf, proof), &expected),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::{account::AccountHash, Digest};
+
+    use super::*;
+
+    fn leaf(nonce: u64) -> [u8; 32] {
+        withdrawal_leaf(
+            &Key::Account(AccountHash::new([7; 32])),
+            U512::from(1_000u64),
+            nonce,
+        )
+        .unwrap()
+    }
+
+    fn root(hash: [u8; 32]) -> U512 {
+        U512::from_big_endian(&hash)
+    }
+
+    #[test]
+    fn leaf_hashes_prefixed_recipient_amount_and_nonce() {
+        let recipient = Key::Account(AccountHash::new([7; 32]));
+        let mut preimage = vec![LEAF_PREFIX];
+        preimage.extend(recipient.to_bytes().unwrap());
+        preimage.extend(U512::from(1_000u64).to_bytes().unwrap());
+        preimage.extend(9u64.to_le_bytes());
+        assert_eq!(leaf(9), Digest::hash(preimage).value());
+        assert_ne!(leaf(9), leaf(10));
+    }
+
+    #[test]
+    fn verifies_a_two_level_tree_from_any_leaf() {
+        let leaves = [leaf(0), leaf(1), leaf(2), leaf(3)];
+        let left = node(&leaves[0], &leaves[1]);
+        let right = node(&leaves[2], &leaves[3]);
+        let top = root(node(&left, &right));
+
+        let siblings = [leaves[1], right].concat();
+        assert!(verify(leaves[0], &MerkleProof::parse(&siblings).unwrap(), top));
+        let siblings = [leaves[2], left].concat();
+        assert!(verify(leaves[3], &MerkleProof::parse(&siblings).unwrap(), top));
+
+        // Wrong leaf, or the right siblings in the wrong level order
+        let siblings = [leaves[1], right].concat();
+        assert!(!verify(leaf(4), &MerkleProof::parse(&siblings).unwrap(), top));
+        let siblings = [right, leaves[1]].concat();
+        assert!(!verify(leaves[0], &MerkleProof::parse(&siblings).unwrap(), top));
+    }
+
+    #[test]
+    fn node_ignores_argument_order_but_not_prefix() {
+        let (a, b) = (leaf(0), leaf(1));
+        assert_eq!(node(&a, &b), node(&b, &a));
+        let mut preimage = vec![LEAF_PREFIX];
+        preimage.extend(a.min(b));
+        preimage.extend(a.max(b));
+        assert_ne!(node(&a, &b), Digest::hash(preimage).value());
+    }
+
+    #[test]
+    fn empty_proof_means_the_leaf_is_the_root() {
+        let proof = MerkleProof::parse(&[]).unwrap();
+        assert!(verify(leaf(0), &proof, root(leaf(0))));
+    }
+
+    #[test]
+    fn root_bytes_is_the_big_endian_low_256_bits() {
+        let mut hash = [0u8; 32];
+        hash[0] = 0xab;
+        hash[31] = 0x01;
+        assert_eq!(root_bytes(root(hash)), Some(hash));
+        assert_eq!(root_bytes(U512::zero()), Some([0; 32]));
+        assert_eq!(root_bytes(U512::one() << 256), None);
+        assert!(!verify(leaf(0), &MerkleProof::parse(&[]).unwrap(), U512::one() << 256));
+    }
+}
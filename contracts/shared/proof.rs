@@ -0,0 +1,100 @@
+//! Proof arguments
+//!
+//! Proofs arrive as `List<U8>`. Each kind is length-checked and decoded here,
+//! before any hashing or pairing work, so a malformed proof fails fast with
+//! `InvalidProofLength` or `InvalidProof`.
+//!
+//! A batch proof is decoded for the proof system the batch declares. Only
+//! Groth16 over BN254 has a verifier in this build; PLONK and STARK proofs
+//! are rejected with `UnsupportedProofSystem` before they are decoded.
+
+use ark_bn254::Bn254;
+use ark_serialize::CanonicalDeserialize;
+
+use crate::{error::Error, types::ProofSystem};
+
+/// Compressed Groth16 proof over BN254: A and C in G1, B in G2
+pub const BATCH_PROOF_LEN: usize = 32 + 64 + 32;
+
+/// Deepest Merkle path accepted for a withdrawal
+pub const MAX_MERKLE_DEPTH: usize = 64;
+
+/// Whether this build can verify proofs from `system`
+pub fn supports(system: ProofSystem) -> bool {
+    match system {
+        ProofSystem::Groth16Bn254 => true,
+        ProofSystem::PlonkKzg | ProofSystem::Stark => false,
+    }
+}
+
+/// A decoded `submit_batch` proof, one variant per supported proof system
+pub enum BatchProof {
+    Groth16Bn254(ark_groth16::Proof<Bn254>),
+}
+
+impl BatchProof {
+    pub fn parse(system: ProofSystem, bytes: &[u8]) -> Result<Self, Error> {
+        match system {
+            ProofSystem::Groth16Bn254 => {
+                if bytes.len() != BATCH_PROOF_LEN {
+                    return Err(Error::InvalidProofLength);
+                }
+                ark_groth16::Proof::deserialize_compressed(bytes)
+                    .map(BatchProof::Groth16Bn254)
+                    .map_err(|_| Error::InvalidProof)
+            }
+            ProofSystem::PlonkKzg | ProofSystem::Stark => Err(Error::UnsupportedProofSystem),
+        }
+    }
+
+    pub fn system(&self) -> ProofSystem {
+        match self {
+            BatchProof::Groth16Bn254(_) => ProofSystem::Groth16Bn254,
+        }
+    }
+}
+
+/// A withdrawal inclusion proof: sibling hashes from leaf to root
+pub struct MerkleProof<'a> {
+    siblings: &'a [u8],
+}
+
+impl<'a> MerkleProof<'a> {
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, Error> {
+        if !bytes.len().is_multiple_of(32) || bytes.len() / 32 > MAX_MERKLE_DEPTH {
+            return Err(Error::InvalidProofLength);
+        }
+        Ok(MerkleProof { siblings: bytes })
+    }
+
+    pub fn depth(&self) -> usize {
+        self.siblings.len() / 32
+    }
+
+    pub fn siblings(&self) -> impl Iterator<Item = &'a [u8; 32]> {
+        // `parse` checked the length is a whole number of hashes
+        self.siblings.as_chunks::<32>().0.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_groth16_proofs_are_decoded() {
+        let bytes = [0u8; BATCH_PROOF_LEN];
+        for system in [ProofSystem::PlonkKzg, ProofSystem::Stark] {
+            assert!(!supports(system));
+            assert_eq!(
+                BatchProof::parse(system, &bytes).err(),
+                Some(Error::UnsupportedProofSystem)
+            );
+        }
+        assert!(supports(ProofSystem::Groth16Bn254));
+        assert_eq!(
+            BatchProof::parse(ProofSystem::Groth16Bn254, &bytes[1..]).err(),
+            Some(Error::InvalidProofLength)
+        );
+    }
+}
@@ -0,0 +1,51 @@
+//! Public inputs of a batch proof
+//!
+//! The roots and forced-queue accumulators a batch is proven against (see
+//! `groth16`), and the digest the contract records for each batch.
+
+use alloc::vec::Vec;
+use casper_types::{
+    bytesrepr::{self, ToBytes},
+    U512,
+};
+
+use crate::hash;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PublicInputs {
+    pub prev_root: U512,
+    pub new_root: U512,
+    /// Forced-queue accumulator before the first request this batch processed
+    pub forced_acc_from: [u8; 32],
+    /// Forced-queue accumulator after the last request this batch processed
+    pub forced_acc_to: [u8; 32],
+}
+
+impl PublicInputs {
+    /// blake2b-256 of the inputs' bytesrepr encoding
+    pub fn digest(&self) -> Result<[u8; 32], bytesrepr::Error> {
+        Ok(hash::blake2b(self.to_bytes()?))
+    }
+}
+
+impl ToBytes for PublicInputs {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut buffer)?;
+        Ok(buffer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.prev_root.serialized_length()
+            + self.new_root.serialized_length()
+            + self.forced_acc_from.serialized_length()
+            + self.forced_acc_to.serialized_length()
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        self.prev_root.write_bytes(writer)?;
+        self.new_root.write_bytes(writer)?;
+        self.forced_acc_from.write_bytes(writer)?;
+        self.forced_acc_to.write_bytes(writer)
+    }
+}
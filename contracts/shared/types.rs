@@ -0,0 +1,187 @@
+//! Domain types stored in contract dictionaries and returned from entry points
+
+use alloc::vec::Vec;
+use casper_types::{
+    bytesrepr::{self, FromBytes, ToBytes, U8_SERIALIZED_LENGTH},
+    CLType, CLTyped,
+};
+
+/// Implements u8-tagged `CLTyped`/`ToBytes`/`FromBytes` for a fieldless enum
+macro_rules! impl_u8_tagged {
+    ($name:ident { $($variant:ident = $tag:expr),+ $(,)? }) => {
+        impl $name {
+            /// Numeric tag used on-chain
+            pub fn tag(self) -> u8 {
+                self as u8
+            }
+
+            /// Parse from the on-chain tag
+            pub fn from_tag(tag: u8) -> Option<Self> {
+                match tag {
+                    $($tag => Some($name::$variant),)+
+                    _ => None,
+                }
+            }
+        }
+
+        impl CLTyped for $name {
+            fn cl_type() -> CLType {
+                CLType::U8
+            }
+        }
+
+        impl ToBytes for $name {
+            fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+                self.tag().to_bytes()
+            }
+
+            fn serialized_length(&self) -> usize {
+                U8_SERIALIZED_LENGTH
+            }
+
+            fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+                writer.push(self.tag());
+                Ok(())
+            }
+        }
+
+        impl FromBytes for $name {
+            fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+                let (tag, rem) = u8::from_bytes(bytes)?;
+                let value = $name::from_tag(tag).ok_or(bytesrepr::Error::Formatting)?;
+                Ok((value, rem))
+            }
+        }
+    };
+}
+
+/// Lifecycle of an L1 deposit
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DepositStatus {
+    /// Funds locked, not yet credited on L2
+    Pending = 0,
+    /// Sequencer has credited the L2 account
+    Credited = 1,
+    /// Returned to the depositor
+    Refunded = 2,
+}
+
+impl_u8_tagged!(DepositStatus {
+    Pending = 0,
+    Credited = 1,
+    Refunded = 2,
+});
+
+/// Lifecycle of a submitted batch
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BatchStatus {
+    /// Accepted, still inside the challenge window
+    Submitted = 0,
+    /// Challenge window passed, root is final
+    Finalized = 1,
+    /// Rolled back
+    Reverted = 2,
+}
+
+impl_u8_tagged!(BatchStatus {
+    Submitted = 0,
+    Finalized = 1,
+    Reverted = 2,
+});
+
+/// Lifecycle of an L2 -> L1 withdrawal
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WithdrawalStatus {
+    /// Proven, waiting out the challenge window
+    Pending = 0,
+    /// Funds paid out
+    Claimed = 1,
+    /// Proven against a disputed root and withdrawn from the queue; the leaf
+    /// can be proven again against a later root
+    Cancelled = 2,
+    /// Left unclaimed past the expiry window, funds moved to the insurance
+    /// fund; can be reactivated within the grace window
+    Expired = 3,
+    /// wCSPR minted to the recipient against it; paid out only to wCSPR
+    /// redeemed against it once it could be finalized
+    Wrapped = 4,
+}
+
+impl_u8_tagged!(WithdrawalStatus {
+    Pending = 0,
+    Claimed = 1,
+    Cancelled = 2,
+    Expired = 3,
+    Wrapped = 4,
+});
+
+/// Proof system a batch proof was produced with
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProofSystem {
+    Groth16Bn254 = 0,
+    PlonkKzg = 1,
+    Stark = 2,
+}
+
+impl_u8_tagged!(ProofSystem {
+    Groth16Bn254 = 0,
+    PlonkKzg = 1,
+    Stark = 2,
+});
+
+/// How the screening list is interpreted
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScreeningMode {
+    /// No checks
+    Disabled = 0,
+    /// Listed accounts are rejected
+    DenyList = 1,
+    /// Only listed accounts are accepted
+    AllowList = 2,
+}
+
+impl_u8_tagged!(ScreeningMode {
+    Disabled = 0,
+    DenyList = 1,
+    AllowList = 2,
+});
+
+/// Privileged operations recorded in the audit log
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AuditAction {
+    SetScreeningMode = 0,
+    SetScreened = 1,
+    SetCompliance = 2,
+    SetEndpointMetadata = 3,
+    SetSequencer = 4,
+    SetPaused = 5,
+    SubmitBatch = 6,
+    DisputeRoot = 7,
+    SetWithdrawalExpiry = 8,
+    SetWcsprToken = 9,
+    SetVerificationKey = 10,
+    SetProofSystem = 11,
+    PostProvingBounty = 12,
+}
+
+impl_u8_tagged!(AuditAction {
+    SetScreeningMode = 0,
+    SetScreened = 1,
+    SetCompliance = 2,
+    SetEndpointMetadata = 3,
+    SetSequencer = 4,
+    SetPaused = 5,
+    SubmitBatch = 6,
+    DisputeRoot = 7,
+    SetWithdrawalExpiry = 8,
+    SetWcsprToken = 9,
+    SetVerificationKey = 10,
+    SetProofSystem = 11,
+    PostProvingBounty = 12,
+});
@@ -0,0 +1,74 @@
+//! Share and fee arithmetic for the fast-exit pool
+//!
+//! Shares are priced on the pool's total assets: its purse balance plus the
+//! withdrawals it has fronted but not yet settled. Both conversions round
+//! down, in the pool's favour. `None` means the arithmetic overflowed or
+//! the pool has shares outstanding but no assets to price them against.
+
+use casper_types::U512;
+
+/// Basis points in one whole
+pub const BPS: u32 = 10_000;
+
+/// Shares minted for depositing `amount` into a pool holding `assets`
+pub fn to_shares(amount: U512, total_shares: U512, assets: U512) -> Option<U512> {
+    if total_shares.is_zero() {
+        return Some(amount);
+    }
+    if assets.is_zero() {
+        return None;
+    }
+    amount.checked_mul(total_shares)?.checked_div(assets)
+}
+
+/// Assets paid out for redeeming `shares`
+pub fn to_assets(shares: U512, total_shares: U512, assets: U512) -> Option<U512> {
+    shares.checked_mul(assets)?.checked_div(total_shares)
+}
+
+/// Pool fee on fronting `amount`, at `fee_bps` basis points
+pub fn fee(amount: U512, fee_bps: u32) -> Option<U512> {
+    amount
+        .checked_mul(U512::from(fee_bps))?
+        .checked_div(U512::from(BPS))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn u(n: u64) -> U512 {
+        U512::from(n)
+    }
+
+    #[test]
+    fn first_deposit_mints_one_share_per_mote() {
+        assert_eq!(to_shares(u(500), U512::zero(), U512::zero()), Some(u(500)));
+    }
+
+    #[test]
+    fn fees_raise_the_share_price() {
+        // 1000 shares backed by 1000 motes plus 100 in fees
+        assert_eq!(to_shares(u(1100), u(1000), u(1100)), Some(u(1000)));
+        assert_eq!(to_assets(u(500), u(1000), u(1100)), Some(u(550)));
+    }
+
+    #[test]
+    fn conversions_round_down() {
+        assert_eq!(to_shares(u(10), u(3), u(7)), Some(u(4)));
+        assert_eq!(to_assets(u(1), u(3), u(7)), Some(u(2)));
+    }
+
+    #[test]
+    fn shares_without_assets_cannot_be_priced() {
+        assert_eq!(to_shares(u(10), u(10), U512::zero()), None);
+        assert_eq!(to_assets(u(10), U512::zero(), u(10)), None);
+    }
+
+    #[test]
+    fn fee_is_in_basis_points() {
+        assert_eq!(fee(u(1_000_000), 30), Some(u(3_000)));
+        assert_eq!(fee(u(1_000_000), BPS), Some(u(1_000_000)));
+        assert_eq!(fee(u(99), 100), Some(U512::zero()));
+    }
+}
@@ -0,0 +1,75 @@
+//! Per-entry-point call counters and proof size telemetry
+//!
+//! Only completed calls are counted: a revert rolls back the increment along
+//! with the rest of the call, so failed attempts never reach chain state.
+//! The same goes for proofs, which are only recorded for accepted batches.
+//! Failure counts therefore cannot be kept here; count failed executions per
+//! entry point off-chain, from the `Failure` results and `User error` codes
+//! the node reports for deploys calling this contract.
+//! Verification cost cannot be measured from inside the contract; take it
+//! from the gas reported in each `submit_batch` execution result.
+
+use crate::store::{self, IndexedDict, TypedDict};
+
+const DICT_CALL_COUNTS: &str = "call_counts";
+const DICT_BATCH_PROOF_SIZES: &str = "batch_proof_sizes";
+/// Sum of accepted proof lengths in bytes
+const KEY_PROOF_BYTES_TOTAL: &str = "proof_bytes_total";
+/// Largest accepted proof length in bytes
+const KEY_PROOF_BYTES_MAX: &str = "proof_bytes_max";
+
+fn counts() -> TypedDict<str, u64> {
+    TypedDict::open(DICT_CALL_COUNTS)
+}
+
+pub fn init() {
+    TypedDict::<str, u64>::create(DICT_CALL_COUNTS);
+    IndexedDict::<u64>::create(DICT_BATCH_PROOF_SIZES);
+    store::new_key(KEY_PROOF_BYTES_TOTAL, 0u64);
+    store::new_key(KEY_PROOF_BYTES_MAX, 0u64);
+}
+
+/// Verify counter storage after install
+pub fn self_check() {
+    store::require_key(DICT_CALL_COUNTS);
+    IndexedDict::<u64>::require(DICT_BATCH_PROOF_SIZES);
+    store::require_key(KEY_PROOF_BYTES_TOTAL);
+    store::require_key(KEY_PROOF_BYTES_MAX);
+}
+
+/// Batches with a recorded proof size
+pub fn proof_count() -> u64 {
+    IndexedDict::<u64>::open(DICT_BATCH_PROOF_SIZES).len()
+}
+
+/// Record the proof length of the next accepted batch
+pub fn record_proof(len: u64) {
+    IndexedDict::<u64>::open(DICT_BATCH_PROOF_SIZES).push(len);
+    let total: u64 = store::read_key(KEY_PROOF_BYTES_TOTAL);
+    store::write_key(KEY_PROOF_BYTES_TOTAL, total + len);
+    let max: u64 = store::read_key(KEY_PROOF_BYTES_MAX);
+    if len > max {
+        store::write_key(KEY_PROOF_BYTES_MAX, len);
+    }
+}
+
+/// `(proofs recorded, total bytes, largest proof in bytes)`
+pub fn proof_stats() -> (u64, u64, u64) {
+    (
+        proof_count(),
+        store::read_key(KEY_PROOF_BYTES_TOTAL),
+        store::read_key(KEY_PROOF_BYTES_MAX),
+    )
+}
+
+/// Count one call to `entry_point`
+pub fn hit(entry_point: &str) {
+    let counts = counts();
+    let count = counts.get(entry_point).unwrap_or(0);
+    counts.set(entry_point, count + 1);
+}
+
+/// Completed calls to an entry point so far
+pub fn get(name: &str) -> u64 {
+    counts().get(name).unwrap_or(0)
+}
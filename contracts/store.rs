@@ -0,0 +1,222 @@
+//! Typed access to named keys and dictionaries
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::marker::PhantomData;
+
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    account::AccountHash,
+    bytesrepr::{FromBytes, ToBytes},
+    CLTyped, URef,
+};
+
+use crate::{error::Error, hash};
+
+/// Create a named key holding `value` in the current context
+pub fn new_key<T: CLTyped + ToBytes>(name: &str, value: T) {
+    let uref = storage::new_uref(value);
+    runtime::put_key(name, uref.into());
+}
+
+/// URef behind a named key
+pub fn key_uref(name: &str) -> URef {
+    runtime::get_key(name)
+        .unwrap_or_revert_with(Error::MissingKey)
+        .into_uref()
+        .unwrap_or_revert_with(Error::InvalidKeyType)
+}
+
+/// Read the value behind a named key
+pub fn read_key<T: CLTyped + FromBytes>(name: &str) -> T {
+    storage::read(key_uref(name))
+        .unwrap_or_revert_with(Error::Serialization)
+        .unwrap_or_revert_with(Error::MissingValue)
+}
+
+/// Overwrite the value behind a named key
+pub fn write_key<T: CLTyped + ToBytes>(name: &str, value: T) {
+    storage::write(key_uref(name), value);
+}
+
+/// Revert with `Error::SelfCheckFailed` unless the named key exists
+pub fn require_key(name: &str) {
+    if !runtime::has_key(name) {
+        runtime::revert(Error::SelfCheckFailed);
+    }
+}
+
+/// Types usable as dictionary item keys
+///
+/// Dictionary item keys are strings of at most 64 bytes, so fixed-size byte
+/// keys are hex-encoded and anything unbounded is hashed first.
+pub trait DictKey {
+    fn dict_key(&self) -> String;
+}
+
+impl DictKey for u64 {
+    fn dict_key(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl DictKey for [u8; 32] {
+    fn dict_key(&self) -> String {
+        hex(self)
+    }
+}
+
+impl DictKey for AccountHash {
+    fn dict_key(&self) -> String {
+        hex(self.as_bytes())
+    }
+}
+
+impl DictKey for str {
+    fn dict_key(&self) -> String {
+        hex(&hash::blake2b(self.as_bytes()))
+    }
+}
+
+impl DictKey for String {
+    fn dict_key(&self) -> String {
+        self.as_str().dict_key()
+    }
+}
+
+/// Ties a dictionary to its key and value types without storing either
+type DictMarker<K, V> = PhantomData<(fn(&K), V)>;
+
+/// A dictionary with typed keys and values
+///
+/// Values are stored as `Option<V>` so entries can be removed; a removed
+/// entry reads back exactly like one that was never written.
+pub struct TypedDict<K: ?Sized, V> {
+    uref: URef,
+    _marker: DictMarker<K, V>,
+}
+
+impl<K: DictKey + ?Sized, V: CLTyped + ToBytes + FromBytes> TypedDict<K, V> {
+    /// Create the dictionary under `name` in the current context
+    pub fn create(name: &str) -> Self {
+        let uref =
+            storage::new_dictionary(name).unwrap_or_revert_with(Error::AlreadyInitialized);
+        TypedDict {
+            uref,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Open a dictionary previously created under `name`
+    pub fn open(name: &str) -> Self {
+        TypedDict {
+            uref: key_uref(name),
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn get(&self, key: &K) -> Option<V> {
+        storage::dictionary_get::<Option<V>>(self.uref, &key.dict_key())
+            .unwrap_or_revert_with(Error::Serialization)
+            .flatten()
+    }
+
+    pub fn set(&self, key: &K, value: V) {
+        storage::dictionary_put(self.uref, &key.dict_key(), Some(value));
+    }
+
+    pub fn remove(&self, key: &K) {
+        storage::dictionary_put::<Option<V>>(self.uref, &key.dict_key(), None);
+    }
+
+    pub fn contains(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+}
+
+/// An append-only dictionary indexed `0..len`, with the length kept under
+/// `<name>_len` so entries can be enumerated off-chain and on-chain
+pub struct IndexedDict<V> {
+    dict: TypedDict<u64, V>,
+    len_key: String,
+}
+
+impl<V: CLTyped + ToBytes + FromBytes> IndexedDict<V> {
+    pub fn create(name: &str) -> Self {
+        let len_key = len_key(name);
+        new_key(&len_key, 0u64);
+        IndexedDict {
+            dict: TypedDict::create(name),
+            len_key,
+        }
+    }
+
+    pub fn open(name: &str) -> Self {
+        IndexedDict {
+            dict: TypedDict::open(name),
+            len_key: len_key(name),
+        }
+    }
+
+    pub fn len(&self) -> u64 {
+        read_key(&self.len_key)
+    }
+
+    /// Revert with `Error::SelfCheckFailed` unless both keys exist
+    pub fn require(name: &str) {
+        require_key(name);
+        require_key(&len_key(name));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get(&self, index: u64) -> Option<V> {
+        self.dict.get(&index)
+    }
+
+    /// Overwrite an existing entry in place
+    pub fn set(&self, index: u64, value: V) {
+        if index >= self.len() {
+            runtime::revert(Error::IndexOutOfBounds);
+        }
+        self.dict.set(&index, value);
+    }
+
+    /// Append and return the new entry's index
+    pub fn push(&self, value: V) -> u64 {
+        let index = self.len();
+        self.dict.set(&index, value);
+        write_key(&self.len_key, index + 1);
+        index
+    }
+
+    /// Entries in `from..to`, clamped to the current length
+    pub fn range(&self, from: u64, to: u64) -> Vec<V> {
+        let to = to.min(self.len());
+        (from..to).filter_map(|i| self.get(i)).collect()
+    }
+}
+
+fn len_key(name: &str) -> String {
+    let mut key = String::from(name);
+    key.push_str("_len");
+    key
+}
+
+/// Lowercase hex of `bytes`
+pub fn hex(bytes: &[u8]) -> String {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+    let mut out = String::with_capacity(bytes.len() * 2);
+    for b in bytes {
+        out.push(DIGITS[(b >> 4) as usize] as char);
+        out.push(DIGITS[(b & 0x0f) as usize] as char);
+    }
+    out
+}
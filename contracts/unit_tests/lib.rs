@@ -0,0 +1,41 @@
+//! Host-side unit tests for the contract's pure logic
+//!
+//! usage: cargo +nightly test -p casper_accelerate_unit_tests
+//!
+//! A test build of the cdylib cannot link off-chain: its `#[no_mangle]`
+//! entry points keep every host import they reach alive. The contract's
+//! modules are compiled into this crate instead, without the entry points,
+//! so their `#[cfg(test)]` tests only link the code they call. The pure
+//! modules the contract shares with `accelerate-verify` are tested in the
+//! shared crate.
+
+extern crate alloc;
+
+#[path = "../abi.rs"]
+pub mod abi;
+#[path = "../args.rs"]
+pub mod args;
+#[path = "../assignment.rs"]
+pub mod assignment;
+#[path = "../bounties.rs"]
+pub mod bounties;
+#[path = "../deposit_address.rs"]
+pub mod deposit_address;
+#[path = "../error.rs"]
+pub mod error;
+#[path = "../forced.rs"]
+pub mod forced;
+#[path = "../public_inputs.rs"]
+pub mod public_inputs;
+#[path = "../shares.rs"]
+pub mod shares;
+#[path = "../store.rs"]
+pub mod store;
+#[path = "../verifier.rs"]
+pub mod verifier;
+#[path = "../wcspr_events.rs"]
+pub mod wcspr_events;
+#[path = "../withdrawals.rs"]
+pub mod withdrawals;
+
+use casper_accelerate_shared::{envelope, hash, merkle, proof, types};
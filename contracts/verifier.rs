@@ -0,0 +1,142 @@
+//! Groth16 verification over BN254
+//!
+//! `init` takes the compressed arkworks verifying key, checks it, prepares it
+//! once and stores the prepared key uncompressed, so each batch only reads
+//! it back without decompressing points or recomputing the G2 line
+//! coefficients.
+//!
+//! Key preparation, the public inputs and the pairing check itself live in
+//! the shared crate's `groth16`, so off-chain verifiers run the same code.
+//! The contract computes the forced-queue accumulators among those inputs
+//! from its own queue, so a proof for a batch that skips a request does
+//! not verify.
+//!
+//! Each key is stored with the circuit identifier `circuit_id(key)`
+//! published alongside it; installing or rotating a key checks the two
+//! agree. The contract hashes withdrawal leaves and the forced queue with
+//! the host's blake2b, so a key is only usable for a circuit whose state
+//! tree is built the same way.
+//!
+//! Each batch declares the proof system its proof uses, and `verify`
+//! dispatches on it. The admin keeps a set of accepted systems; a migration
+//! accepts the new system alongside the old one for a transition window,
+//! then drops the old one. Only systems with a routine here can be accepted.
+
+use alloc::vec::Vec;
+use ark_bn254::Bn254;
+use ark_groth16::PreparedVerifyingKey;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Compress, Validate};
+use casper_accelerate_shared::{
+    groth16::{check, public_inputs, validate},
+    types::ProofSystem,
+};
+use casper_contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
+use casper_types::bytesrepr::Bytes;
+
+use crate::{
+    error::Error,
+    proof::{self, BatchProof},
+    public_inputs::PublicInputs,
+    store::{self, TypedDict},
+};
+
+const KEY_PREPARED_VK: &str = "prepared_verifying_key";
+pub const KEY_CIRCUIT_ID: &str = "circuit_id";
+/// Whether new batches may use a proof system, by tag
+pub const DICT_PROOF_SYSTEMS: &str = "accepted_proof_systems";
+
+fn read_prepared(validate: Validate) -> Option<PreparedVerifyingKey<Bn254>> {
+    let bytes: Bytes = store::read_key(KEY_PREPARED_VK);
+    PreparedVerifyingKey::deserialize_with_mode(bytes.as_slice(), Compress::No, validate).ok()
+}
+
+fn serialize_prepared(vk: &[u8], id: [u8; 32]) -> Bytes {
+    let pvk = validate(vk, id).unwrap_or_else(|e| runtime::revert(e));
+    let mut prepared = Vec::with_capacity(pvk.uncompressed_size());
+    pvk.serialize_uncompressed(&mut prepared)
+        .ok()
+        .unwrap_or_revert_with(Error::Serialization);
+    Bytes::from(prepared)
+}
+
+/// Prepare and store the verifying key, reverting unless it decodes, has the
+/// expected number of public inputs and matches `id`
+pub fn init(vk: Bytes, id: [u8; 32]) {
+    store::new_key(KEY_PREPARED_VK, serialize_prepared(&vk, id));
+    store::new_key(KEY_CIRCUIT_ID, id);
+    TypedDict::<u64, bool>::create(DICT_PROOF_SYSTEMS);
+    set_accepted(ProofSystem::Groth16Bn254, true);
+}
+
+/// Replace the verifying key, checked as in `init`; later batches are
+/// verified against the new key
+pub fn set(vk: Bytes, id: [u8; 32]) {
+    store::write_key(KEY_PREPARED_VK, serialize_prepared(&vk, id));
+    store::write_key(KEY_CIRCUIT_ID, id);
+}
+
+/// Verify verifier storage after install, fully validating the stored key
+pub fn self_check() {
+    store::require_key(KEY_PREPARED_VK);
+    store::require_key(DICT_PROOF_SYSTEMS);
+    let _id: [u8; 32] = store::read_key(KEY_CIRCUIT_ID);
+    if read_prepared(Validate::Yes).is_none() {
+        runtime::revert(Error::SelfCheckFailed);
+    }
+}
+
+fn proof_systems() -> TypedDict<u64, bool> {
+    TypedDict::open(DICT_PROOF_SYSTEMS)
+}
+
+/// Whether new batches may use `system`
+pub fn accepts(system: ProofSystem) -> bool {
+    proof_systems()
+        .get(&u64::from(system.tag()))
+        .unwrap_or(false)
+}
+
+/// Accept or stop accepting batches proven with `system`, reverting when
+/// accepting a system this build cannot verify
+pub fn set_accepted(system: ProofSystem, accepted: bool) {
+    if accepted && !proof::supports(system) {
+        runtime::revert(Error::UnsupportedProofSystem);
+    }
+    proof_systems().set(&u64::from(system.tag()), accepted);
+}
+
+/// Whether `proof` is a valid proof of the batch with public inputs
+/// `inputs` under its proof system
+///
+/// The stored key was validated at install, so it is read back unchecked.
+pub fn verify(proof: &BatchProof, inputs: &PublicInputs) -> bool {
+    let Some(inputs) = public_inputs(inputs) else {
+        return false;
+    };
+    match proof {
+        BatchProof::Groth16Bn254(proof) => {
+            let pvk = read_prepared(Validate::No).unwrap_or_revert_with(Error::Serialization);
+            check(&pvk, proof, &inputs)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_accelerate_shared::groth16::{prepare, testing::stand_in_keys};
+
+    use super::*;
+
+    #[test]
+    fn prepared_key_survives_storage_encoding() {
+        let pvk = prepare(&stand_in_keys().0).unwrap();
+        let mut bytes = Vec::new();
+        pvk.serialize_uncompressed(&mut bytes).unwrap();
+        for validate in [Validate::Yes, Validate::No] {
+            let decoded =
+                PreparedVerifyingKey::deserialize_with_mode(&bytes[..], Compress::No, validate)
+                    .unwrap();
+            assert_eq!(decoded, pvk);
+        }
+    }
+}
@@ -0,0 +1,334 @@
+//! wCSPR: a CEP-18 token minted against pending bridge withdrawals
+//!
+//! A withdrawal recipient who does not want to wait out the challenge window
+//! wraps it on the bridge, which mints the same amount of wCSPR here. wCSPR
+//! trades like any CEP-18 token; the bridge burns it again when a holder
+//! redeems it for CSPR. Only the bridge package named at install may mint
+//! or burn, checked against the immediate caller, so a bridge upgrade keeps
+//! the right. Amounts are in motes, hence 9 decimals.
+//!
+//! Storage and events follow the CEP-18 layout so wallets and indexers read
+//! the token without special casing: `name`, `symbol`, `decimals` and
+//! `total_supply` named keys, a `balances` dictionary keyed by the base64 of
+//! the holder `Key`'s bytes, an `allowances` dictionary keyed by the hex
+//! blake2b of the owner and spender keys, and events in `wcspr_events`.
+//! Holders are the immediate caller, accounts or contract packages, so a
+//! contract the user calls cannot move the user's tokens.
+//!
+//! Install after the bridge with `minter` set to the bridge's package hash,
+//! then point the bridge at this package with `set_wcspr_token`.
+
+#![no_std]
+#![no_main]
+#![feature(core_intrinsics)]
+#![allow(internal_features)]
+
+extern crate alloc;
+
+#[cfg(not(test))]
+#[global_allocator]
+static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
+
+#[cfg(not(test))]
+#[panic_handler]
+fn panic(_info: &core::panic::PanicInfo) -> ! {
+    core::intrinsics::abort()
+}
+
+#[allow(dead_code)]
+mod abi;
+mod caller;
+#[allow(dead_code)]
+mod error;
+#[allow(dead_code)]
+mod network;
+#[allow(dead_code)]
+mod store;
+mod wcspr_entry_points;
+mod wcspr_events;
+
+use alloc::string::String;
+use casper_accelerate_shared::hash;
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    bytesrepr::{FromBytes, ToBytes},
+    runtime_args, CLTyped, CLValue, Key, U256,
+};
+
+use error::Error;
+use network::{WCSPR_HASH_NAME, WCSPR_PACKAGE_NAME};
+use wcspr_entry_points::*;
+use wcspr_events::Event;
+
+const NAME: &str = "Wrapped CSPR";
+const SYMBOL: &str = "WCSPR";
+const DECIMALS: u8 = 9;
+
+const KEY_NAME: &str = "name";
+const KEY_SYMBOL: &str = "symbol";
+const KEY_DECIMALS: &str = "decimals";
+const KEY_TOTAL_SUPPLY: &str = "total_supply";
+const KEY_MINTER: &str = "minter";
+const DICT_BALANCES: &str = "balances";
+const DICT_ALLOWANCES: &str = "allowances";
+
+/// `balances` item key of `owner`
+fn balance_key(owner: &Key) -> String {
+    base64::encode(owner.to_bytes().unwrap_or_revert_with(Error::Serialization))
+}
+
+/// `allowances` item key of `owner`'s allowance to `spender`
+fn allowance_key(owner: &Key, spender: &Key) -> String {
+    let mut preimage = owner.to_bytes().unwrap_or_revert_with(Error::Serialization);
+    spender
+        .write_bytes(&mut preimage)
+        .unwrap_or_revert_with(Error::Serialization);
+    store::hex(&hash::blake2b(preimage))
+}
+
+fn read_item<T: CLTyped + FromBytes + Default>(dict: &str, item: &str) -> T {
+    storage::dictionary_get(store::key_uref(dict), item)
+        .unwrap_or_revert_with(Error::Serialization)
+        .unwrap_or_default()
+}
+
+fn write_item(dict: &str, item: &str, value: U256) {
+    storage::dictionary_put(store::key_uref(dict), item, value);
+}
+
+fn balance(owner: &Key) -> U256 {
+    read_item(DICT_BALANCES, &balance_key(owner))
+}
+
+fn set_balance(owner: &Key, amount: U256) {
+    write_item(DICT_BALANCES, &balance_key(owner), amount);
+}
+
+fn allowance_of(owner: &Key, spender: &Key) -> U256 {
+    read_item(DICT_ALLOWANCES, &allowance_key(owner, spender))
+}
+
+fn set_allowance(owner: &Key, spender: &Key, amount: U256) {
+    write_item(DICT_ALLOWANCES, &allowance_key(owner, spender), amount);
+}
+
+fn move_balance(from: &Key, to: &Key, amount: U256) {
+    let from_balance = balance(from)
+        .checked_sub(amount)
+        .unwrap_or_revert_with(Error::InsufficientWcspr);
+    set_balance(from, from_balance);
+    let to_balance = balance(to)
+        .checked_add(amount)
+        .unwrap_or_revert_with(Error::InvalidAmount);
+    set_balance(to, to_balance);
+}
+
+fn ret<T: CLTyped + ToBytes>(value: T) -> ! {
+    runtime::ret(CLValue::from_t(value).unwrap_or_revert_with(Error::Serialization))
+}
+
+/// Revert unless the immediate caller is the bridge package
+fn require_minter() {
+    let minter: [u8; 32] = store::read_key(KEY_MINTER);
+    if caller::package() != Some(minter) {
+        runtime::revert(Error::NotBridge);
+    }
+}
+
+/// Create token storage; `minter` is the bridge's package hash
+/// Arguments: minter (Key)
+#[no_mangle]
+pub extern "C" fn init() {
+    if runtime::has_key(KEY_TOTAL_SUPPLY) {
+        runtime::revert(Error::AlreadyInitialized);
+    }
+    let minter: Key = runtime::get_named_arg(ARG_MINTER);
+    let minter = minter
+        .into_hash_addr()
+        .unwrap_or_revert_with(Error::InvalidArgument);
+    store::new_key(KEY_MINTER, minter);
+    store::new_key(KEY_NAME, String::from(NAME));
+    store::new_key(KEY_SYMBOL, String::from(SYMBOL));
+    store::new_key(KEY_DECIMALS, DECIMALS);
+    store::new_key(KEY_TOTAL_SUPPLY, U256::zero());
+    for dict in [DICT_BALANCES, DICT_ALLOWANCES] {
+        storage::new_dictionary(dict).unwrap_or_revert_with(Error::AlreadyInitialized);
+    }
+    wcspr_events::init();
+}
+
+#[no_mangle]
+pub extern "C" fn name() {
+    ret(store::read_key::<String>(KEY_NAME))
+}
+
+#[no_mangle]
+pub extern "C" fn symbol() {
+    ret(store::read_key::<String>(KEY_SYMBOL))
+}
+
+#[no_mangle]
+pub extern "C" fn decimals() {
+    ret(store::read_key::<u8>(KEY_DECIMALS))
+}
+
+#[no_mangle]
+pub extern "C" fn total_supply() {
+    ret(store::read_key::<U256>(KEY_TOTAL_SUPPLY))
+}
+
+/// Arguments: address (Key)
+#[no_mangle]
+pub extern "C" fn balance_of() {
+    let address: Key = runtime::get_named_arg(ARG_ADDRESS);
+    ret(balance(&address))
+}
+
+/// Arguments: owner (Key), spender (Key)
+#[no_mangle]
+pub extern "C" fn allowance() {
+    let owner: Key = runtime::get_named_arg(ARG_OWNER);
+    let spender: Key = runtime::get_named_arg(ARG_SPENDER);
+    ret(allowance_of(&owner, &spender))
+}
+
+/// Let `spender` move up to `amount` of the caller's wCSPR
+/// Arguments: spender (Key), amount (U256)
+#[no_mangle]
+pub extern "C" fn approve() {
+    let owner = caller::immediate();
+    let spender: Key = runtime::get_named_arg(ARG_SPENDER);
+    let amount: U256 = runtime::get_named_arg(ARG_AMOUNT);
+    set_allowance(&owner, &spender, amount);
+    wcspr_events::emit(Event::SetAllowance {
+        owner,
+        spender,
+        allowance: amount,
+    });
+}
+
+/// Arguments: spender (Key), amount (U256)
+#[no_mangle]
+pub extern "C" fn increase_allowance() {
+    let owner = caller::immediate();
+    let spender: Key = runtime::get_named_arg(ARG_SPENDER);
+    let amount: U256 = runtime::get_named_arg(ARG_AMOUNT);
+    let allowance = allowance_of(&owner, &spender).saturating_add(amount);
+    set_allowance(&owner, &spender, allowance);
+    wcspr_events::emit(Event::IncreaseAllowance {
+        owner,
+        spender,
+        allowance,
+        inc_by: amount,
+    });
+}
+
+/// Arguments: spender (Key), amount (U256)
+#[no_mangle]
+pub extern "C" fn decrease_allowance() {
+    let owner = caller::immediate();
+    let spender: Key = runtime::get_named_arg(ARG_SPENDER);
+    let amount: U256 = runtime::get_named_arg(ARG_AMOUNT);
+    let allowance = allowance_of(&owner, &spender).saturating_sub(amount);
+    set_allowance(&owner, &spender, allowance);
+    wcspr_events::emit(Event::DecreaseAllowance {
+        owner,
+        spender,
+        allowance,
+        decr_by: amount,
+    });
+}
+
+/// Arguments: recipient (Key), amount (U256)
+#[no_mangle]
+pub extern "C" fn transfer() {
+    let sender = caller::immediate();
+    let recipient: Key = runtime::get_named_arg(ARG_RECIPIENT);
+    let amount: U256 = runtime::get_named_arg(ARG_AMOUNT);
+    move_balance(&sender, &recipient, amount);
+    wcspr_events::emit(Event::Transfer {
+        sender,
+        recipient,
+        amount,
+    });
+}
+
+/// Move `owner`'s wCSPR within the caller's allowance
+/// Arguments: owner (Key), recipient (Key), amount (U256)
+#[no_mangle]
+pub extern "C" fn transfer_from() {
+    let spender = caller::immediate();
+    let owner: Key = runtime::get_named_arg(ARG_OWNER);
+    let recipient: Key = runtime::get_named_arg(ARG_RECIPIENT);
+    let amount: U256 = runtime::get_named_arg(ARG_AMOUNT);
+    let remaining = allowance_of(&owner, &spender)
+        .checked_sub(amount)
+        .unwrap_or_revert_with(Error::InsufficientAllowance);
+    set_allowance(&owner, &spender, remaining);
+    move_balance(&owner, &recipient, amount);
+    wcspr_events::emit(Event::TransferFrom {
+        spender,
+        owner,
+        recipient,
+        amount,
+    });
+}
+
+/// Bridge only: mint against a wrapped withdrawal
+/// Arguments: owner (Key), amount (U256)
+#[no_mangle]
+pub extern "C" fn mint() {
+    require_minter();
+    let owner: Key = runtime::get_named_arg(ARG_OWNER);
+    let amount: U256 = runtime::get_named_arg(ARG_AMOUNT);
+    let supply: U256 = store::read_key(KEY_TOTAL_SUPPLY);
+    let supply = supply
+        .checked_add(amount)
+        .unwrap_or_revert_with(Error::InvalidAmount);
+    store::write_key(KEY_TOTAL_SUPPLY, supply);
+    set_balance(&owner, balance(&owner) + amount);
+    wcspr_events::emit(Event::Mint {
+        recipient: owner,
+        amount,
+    });
+}
+
+/// Bridge only: burn wCSPR redeemed for CSPR
+/// Arguments: owner (Key), amount (U256)
+#[no_mangle]
+pub extern "C" fn burn() {
+    require_minter();
+    let owner: Key = runtime::get_named_arg(ARG_OWNER);
+    let amount: U256 = runtime::get_named_arg(ARG_AMOUNT);
+    let remaining = balance(&owner)
+        .checked_sub(amount)
+        .unwrap_or_revert_with(Error::InsufficientWcspr);
+    set_balance(&owner, remaining);
+    let supply: U256 = store::read_key(KEY_TOTAL_SUPPLY);
+    store::write_key(KEY_TOTAL_SUPPLY, supply - amount);
+    wcspr_events::emit(Event::Burn { owner, amount });
+}
+
+/// Token installation
+/// Arguments: minter (Key, the bridge's contract package hash)
+#[no_mangle]
+pub extern "C" fn call() {
+    let (contract_hash, _) = storage::new_contract(
+        abi::entry_points(ENTRY_POINTS),
+        None,
+        Some(String::from(WCSPR_PACKAGE_NAME)),
+        None,
+        None,
+    );
+    runtime::put_key(WCSPR_HASH_NAME, contract_hash.into());
+
+    let minter: Key = runtime::get_named_arg(ARG_MINTER);
+    runtime::call_contract::<()>(
+        contract_hash,
+        EP_INIT,
+        runtime_args! { ARG_MINTER => minter },
+    );
+}
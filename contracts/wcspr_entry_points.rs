@@ -0,0 +1,145 @@
+//! Entry points of the wCSPR token (see `wcspr.rs`)
+//!
+//! Names, arguments and types follow CEP-18. Shared with the bridge, which
+//! calls `mint` and `burn` by these names.
+
+use crate::abi::{arg, Access, ArgType, EntryPointDef};
+
+pub const EP_INIT: &str = "init";
+pub const EP_NAME: &str = "name";
+pub const EP_SYMBOL: &str = "symbol";
+pub const EP_DECIMALS: &str = "decimals";
+pub const EP_TOTAL_SUPPLY: &str = "total_supply";
+pub const EP_BALANCE_OF: &str = "balance_of";
+pub const EP_ALLOWANCE: &str = "allowance";
+pub const EP_APPROVE: &str = "approve";
+pub const EP_INCREASE_ALLOWANCE: &str = "increase_allowance";
+pub const EP_DECREASE_ALLOWANCE: &str = "decrease_allowance";
+pub const EP_TRANSFER: &str = "transfer";
+pub const EP_TRANSFER_FROM: &str = "transfer_from";
+pub const EP_MINT: &str = "mint";
+pub const EP_BURN: &str = "burn";
+
+pub const ARG_MINTER: &str = "minter";
+pub const ARG_ADDRESS: &str = "address";
+pub const ARG_OWNER: &str = "owner";
+pub const ARG_SPENDER: &str = "spender";
+pub const ARG_RECIPIENT: &str = "recipient";
+pub const ARG_AMOUNT: &str = "amount";
+
+pub const ENTRY_POINTS: &[EntryPointDef] = &[
+    // init(minter: Key)
+    EntryPointDef {
+        name: EP_INIT,
+        args: &[arg(ARG_MINTER, ArgType::Key)],
+        ret: ArgType::Unit,
+        access: Access::Public,
+    },
+    // name() -> String
+    EntryPointDef {
+        name: EP_NAME,
+        args: &[],
+        ret: ArgType::String,
+        access: Access::Public,
+    },
+    // symbol() -> String
+    EntryPointDef {
+        name: EP_SYMBOL,
+        args: &[],
+        ret: ArgType::String,
+        access: Access::Public,
+    },
+    // decimals() -> u8
+    EntryPointDef {
+        name: EP_DECIMALS,
+        args: &[],
+        ret: ArgType::U8,
+        access: Access::Public,
+    },
+    // total_supply() -> U256
+    EntryPointDef {
+        name: EP_TOTAL_SUPPLY,
+        args: &[],
+        ret: ArgType::U256,
+        access: Access::Public,
+    },
+    // balance_of(address: Key) -> U256
+    EntryPointDef {
+        name: EP_BALANCE_OF,
+        args: &[arg(ARG_ADDRESS, ArgType::Key)],
+        ret: ArgType::U256,
+        access: Access::Public,
+    },
+    // allowance(owner: Key, spender: Key) -> U256
+    EntryPointDef {
+        name: EP_ALLOWANCE,
+        args: &[arg(ARG_OWNER, ArgType::Key), arg(ARG_SPENDER, ArgType::Key)],
+        ret: ArgType::U256,
+        access: Access::Public,
+    },
+    // approve(spender: Key, amount: U256)
+    EntryPointDef {
+        name: EP_APPROVE,
+        args: &[
+            arg(ARG_SPENDER, ArgType::Key),
+            arg(ARG_AMOUNT, ArgType::U256),
+        ],
+        ret: ArgType::Unit,
+        access: Access::Public,
+    },
+    // increase_allowance(spender: Key, amount: U256)
+    EntryPointDef {
+        name: EP_INCREASE_ALLOWANCE,
+        args: &[
+            arg(ARG_SPENDER, ArgType::Key),
+            arg(ARG_AMOUNT, ArgType::U256),
+        ],
+        ret: ArgType::Unit,
+        access: Access::Public,
+    },
+    // decrease_allowance(spender: Key, amount: U256)
+    EntryPointDef {
+        name: EP_DECREASE_ALLOWANCE,
+        args: &[
+            arg(ARG_SPENDER, ArgType::Key),
+            arg(ARG_AMOUNT, ArgType::U256),
+        ],
+        ret: ArgType::Unit,
+        access: Access::Public,
+    },
+    // transfer(recipient: Key, amount: U256)
+    EntryPointDef {
+        name: EP_TRANSFER,
+        args: &[
+            arg(ARG_RECIPIENT, ArgType::Key),
+            arg(ARG_AMOUNT, ArgType::U256),
+        ],
+        ret: ArgType::Unit,
+        access: Access::Public,
+    },
+    // transfer_from(owner: Key, recipient: Key, amount: U256)
+    EntryPointDef {
+        name: EP_TRANSFER_FROM,
+        args: &[
+            arg(ARG_OWNER, ArgType::Key),
+            arg(ARG_RECIPIENT, ArgType::Key),
+            arg(ARG_AMOUNT, ArgType::U256),
+        ],
+        ret: ArgType::Unit,
+        access: Access::Public,
+    },
+    // mint(owner: Key, amount: U256)
+    EntryPointDef {
+        name: EP_MINT,
+        args: &[arg(ARG_OWNER, ArgType::Key), arg(ARG_AMOUNT, ArgType::U256)],
+        ret: ArgType::Unit,
+        access: Access::Bridge,
+    },
+    // burn(owner: Key, amount: U256)
+    EntryPointDef {
+        name: EP_BURN,
+        args: &[arg(ARG_OWNER, ArgType::Key), arg(ARG_AMOUNT, ArgType::U256)],
+        ret: ArgType::Unit,
+        access: Access::Bridge,
+    },
+];
@@ -0,0 +1,368 @@
+//! CEP-18 events of the wCSPR token (see `wcspr.rs`)
+//!
+//! Published the way CEP-18 tokens publish them, in the Casper Event
+//! Standard layout wallets and indexers read: each event is stored in the
+//! `__events` dictionary under its index as `Bytes`, the string
+//! `event_<Name>` followed by its fields, and `__events_schema` lists every
+//! event's field names and types.
+
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+    vec::Vec,
+};
+use casper_contract::{contract_api::storage, unwrap_or_revert::UnwrapOrRevert};
+use casper_types::{
+    bytesrepr::{self, Bytes, ToBytes, U32_SERIALIZED_LENGTH},
+    CLType, CLTyped, CLValue, Key, U256,
+};
+
+use crate::{error::Error, store};
+
+const KEY_EVENTS: &str = "__events";
+const KEY_EVENTS_LENGTH: &str = "__events_length";
+const KEY_EVENTS_SCHEMA: &str = "__events_schema";
+const KEY_CES_VERSION: &str = "__events_ces_version";
+const CES_VERSION: &str = "0.1.0";
+
+/// Field names and types of every event, sorted by event name as the
+/// standard's schema map is
+const SCHEMAS: &[(&str, &[(&str, CLType)])] = &[
+    ("Burn", &[("owner", CLType::Key), ("amount", CLType::U256)]),
+    (
+        "DecreaseAllowance",
+        &[
+            ("owner", CLType::Key),
+            ("spender", CLType::Key),
+            ("allowance", CLType::U256),
+            ("decr_by", CLType::U256),
+        ],
+    ),
+    (
+        "IncreaseAllowance",
+        &[
+            ("owner", CLType::Key),
+            ("spender", CLType::Key),
+            ("allowance", CLType::U256),
+            ("inc_by", CLType::U256),
+        ],
+    ),
+    (
+        "Mint",
+        &[("recipient", CLType::Key), ("amount", CLType::U256)],
+    ),
+    (
+        "SetAllowance",
+        &[
+            ("owner", CLType::Key),
+            ("spender", CLType::Key),
+            ("allowance", CLType::U256),
+        ],
+    ),
+    (
+        "Transfer",
+        &[
+            ("sender", CLType::Key),
+            ("recipient", CLType::Key),
+            ("amount", CLType::U256),
+        ],
+    ),
+    (
+        "TransferFrom",
+        &[
+            ("spender", CLType::Key),
+            ("owner", CLType::Key),
+            ("recipient", CLType::Key),
+            ("amount", CLType::U256),
+        ],
+    ),
+];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Event {
+    Mint {
+        recipient: Key,
+        amount: U256,
+    },
+    Burn {
+        owner: Key,
+        amount: U256,
+    },
+    SetAllowance {
+        owner: Key,
+        spender: Key,
+        allowance: U256,
+    },
+    IncreaseAllowance {
+        owner: Key,
+        spender: Key,
+        allowance: U256,
+        inc_by: U256,
+    },
+    DecreaseAllowance {
+        owner: Key,
+        spender: Key,
+        allowance: U256,
+        decr_by: U256,
+    },
+    Transfer {
+        sender: Key,
+        recipient: Key,
+        amount: U256,
+    },
+    TransferFrom {
+        spender: Key,
+        owner: Key,
+        recipient: Key,
+        amount: U256,
+    },
+}
+
+impl Event {
+    /// Name the schema lists the event under
+    pub fn name(&self) -> &'static str {
+        match self {
+            Event::Mint { .. } => "Mint",
+            Event::Burn { .. } => "Burn",
+            Event::SetAllowance { .. } => "SetAllowance",
+            Event::IncreaseAllowance { .. } => "IncreaseAllowance",
+            Event::DecreaseAllowance { .. } => "DecreaseAllowance",
+            Event::Transfer { .. } => "Transfer",
+            Event::TransferFrom { .. } => "TransferFrom",
+        }
+    }
+
+    /// `event_<Name>` followed by the fields in schema order
+    pub fn encode(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut name = String::from("event_");
+        name.push_str(self.name());
+        let mut out = name.to_bytes()?;
+        match self {
+            Event::Mint { recipient, amount } => {
+                recipient.write_bytes(&mut out)?;
+                amount.write_bytes(&mut out)?;
+            }
+            Event::Burn { owner, amount } => {
+                owner.write_bytes(&mut out)?;
+                amount.write_bytes(&mut out)?;
+            }
+            Event::SetAllowance {
+                owner,
+                spender,
+                allowance,
+            } => {
+                owner.write_bytes(&mut out)?;
+                spender.write_bytes(&mut out)?;
+                allowance.write_bytes(&mut out)?;
+            }
+            Event::IncreaseAllowance {
+                owner,
+                spender,
+                allowance,
+                inc_by: change,
+            }
+            | Event::DecreaseAllowance {
+                owner,
+                spender,
+                allowance,
+                decr_by: change,
+            } => {
+                owner.write_bytes(&mut out)?;
+                spender.write_bytes(&mut out)?;
+                allowance.write_bytes(&mut out)?;
+                change.write_bytes(&mut out)?;
+            }
+            Event::Transfer {
+                sender,
+                recipient,
+                amount,
+            } => {
+                sender.write_bytes(&mut out)?;
+                recipient.write_bytes(&mut out)?;
+                amount.write_bytes(&mut out)?;
+            }
+            Event::TransferFrom {
+                spender,
+                owner,
+                recipient,
+                amount,
+            } => {
+                spender.write_bytes(&mut out)?;
+                owner.write_bytes(&mut out)?;
+                recipient.write_bytes(&mut out)?;
+                amount.write_bytes(&mut out)?;
+            }
+        }
+        Ok(out)
+    }
+}
+
+/// `SCHEMAS` as the standard stores it: `Map<String, List<(String, CLType)>>`
+struct Schemas;
+
+impl CLTyped for Schemas {
+    fn cl_type() -> CLType {
+        CLType::Map {
+            key: Box::new(CLType::String),
+            value: Box::new(CLType::List(Box::new(CLType::Tuple2([
+                Box::new(CLType::String),
+                Box::new(CLType::Any),
+            ])))),
+        }
+    }
+}
+
+impl ToBytes for Schemas {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut out = Vec::with_capacity(self.serialized_length());
+        self.write_bytes(&mut out)?;
+        Ok(out)
+    }
+
+    fn serialized_length(&self) -> usize {
+        let fields = |fields: &[(&str, CLType)]| -> usize {
+            fields
+                .iter()
+                .map(|(name, ty)| name.serialized_length() + ty.serialized_length())
+                .sum()
+        };
+        U32_SERIALIZED_LENGTH
+            + SCHEMAS
+                .iter()
+                .map(|(name, f)| name.serialized_length() + U32_SERIALIZED_LENGTH + fields(f))
+                .sum::<usize>()
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        (SCHEMAS.len() as u32).write_bytes(writer)?;
+        for (name, fields) in SCHEMAS {
+            name.write_bytes(writer)?;
+            (fields.len() as u32).write_bytes(writer)?;
+            for (field, ty) in *fields {
+                field.write_bytes(writer)?;
+                write_cl_type(ty, writer)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Append the bytesrepr encoding of `ty`
+///
+/// casper-types keeps `CLType`'s encoder private, but an empty `CLValue` of
+/// the type encodes as a zero `u32` length followed by exactly that.
+fn write_cl_type(ty: &CLType, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+    let value = CLValue::from_components(ty.clone(), Vec::new()).to_bytes()?;
+    writer.extend_from_slice(&value[U32_SERIALIZED_LENGTH..]);
+    Ok(())
+}
+
+/// Create the event dictionary and publish the schema
+pub fn init() {
+    storage::new_dictionary(KEY_EVENTS).unwrap_or_revert_with(Error::AlreadyInitialized);
+    store::new_key(KEY_EVENTS_LENGTH, 0u32);
+    store::new_key(KEY_EVENTS_SCHEMA, Schemas);
+    store::new_key(KEY_CES_VERSION, String::from(CES_VERSION));
+}
+
+/// Append `event` to `__events`
+pub fn emit(event: Event) {
+    let bytes = event.encode().unwrap_or_revert_with(Error::Serialization);
+    let index: u32 = store::read_key(KEY_EVENTS_LENGTH);
+    storage::dictionary_put(
+        store::key_uref(KEY_EVENTS),
+        &index.to_string(),
+        Bytes::from(bytes),
+    );
+    store::write_key(KEY_EVENTS_LENGTH, index + 1);
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use casper_types::{account::AccountHash, bytesrepr::FromBytes};
+
+    use super::*;
+
+    fn key(byte: u8) -> Key {
+        Key::Account(AccountHash::new([byte; 32]))
+    }
+
+    #[test]
+    fn schemas_are_sorted_and_match_the_events() {
+        let names: Vec<_> = SCHEMAS.iter().map(|(name, _)| *name).collect();
+        let mut sorted = names.clone();
+        sorted.sort_unstable();
+        assert_eq!(names, sorted);
+
+        let events = vec![
+            Event::Mint {
+                recipient: key(1),
+                amount: U256::one(),
+            },
+            Event::Burn {
+                owner: key(1),
+                amount: U256::one(),
+            },
+            Event::SetAllowance {
+                owner: key(1),
+                spender: key(2),
+                allowance: U256::one(),
+            },
+            Event::IncreaseAllowance {
+                owner: key(1),
+                spender: key(2),
+                allowance: U256::one(),
+                inc_by: U256::one(),
+            },
+            Event::DecreaseAllowance {
+                owner: key(1),
+                spender: key(2),
+                allowance: U256::one(),
+                decr_by: U256::one(),
+            },
+            Event::Transfer {
+                sender: key(1),
+                recipient: key(2),
+                amount: U256::one(),
+            },
+            Event::TransferFrom {
+                spender: key(3),
+                owner: key(1),
+                recipient: key(2),
+                amount: U256::one(),
+            },
+        ];
+        for event in events {
+            let (_, fields) = SCHEMAS
+                .iter()
+                .find(|(name, _)| *name == event.name())
+                .unwrap();
+            // Every field here is a Key (33 bytes) or a U256 of 1 (2 bytes)
+            let expected: usize = fields
+                .iter()
+                .map(|(_, ty)| if *ty == CLType::Key { 33 } else { 2 })
+                .sum();
+            let bytes = event.encode().unwrap();
+            let (name, rem) = String::from_bytes(&bytes).unwrap();
+            assert_eq!(name, ["event_", event.name()].concat());
+            assert_eq!(rem.len(), expected, "{}", event.name());
+        }
+    }
+
+    #[test]
+    fn schema_encodes_as_a_string_map() {
+        let bytes = Schemas.to_bytes().unwrap();
+        assert_eq!(bytes.len(), Schemas.serialized_length());
+        let (count, rem) = u32::from_bytes(&bytes).unwrap();
+        assert_eq!(count as usize, SCHEMAS.len());
+        let (first, rem) = String::from_bytes(rem).unwrap();
+        assert_eq!(first, "Burn");
+        let (fields, rem) = u32::from_bytes(rem).unwrap();
+        assert_eq!(fields, 2);
+        let (field, rem) = String::from_bytes(rem).unwrap();
+        assert_eq!(field, "owner");
+        assert_eq!(CLType::from_bytes(rem).unwrap().0, CLType::Key);
+    }
+}
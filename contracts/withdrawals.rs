@@ -0,0 +1,411 @@
+//! Pending withdrawals and the challenge window
+//!
+//! `withdraw` only proves and queues a withdrawal. Funds move when
+//! `finalize_withdrawal` is called after the challenge window, measured in
+//! blocks from the request. Within that window a watcher who finds a bad
+//! root asks the admin to `dispute_root` it: withdrawals proven against a
+//! disputed root can never be finalized, and anyone may cancel them so
+//! their leaves can be proven again against a corrected root.
+//!
+//! Expiry is an optional policy, off until the admin sets an expiry window.
+//! A withdrawal still unclaimed that many blocks after its challenge window
+//! closes can be expired by anyone, moving its funds to the insurance fund
+//! so the bridge purse does not carry stale liabilities forever. Until the
+//! grace window after expiry closes, anyone can reactivate it, bringing the
+//! funds back so it can be finalized as before.
+//!
+//! A recipient can also wrap its pending withdrawal for wCSPR (see
+//! `wrapped`) or assign it to another recipient (see `assignment`). A
+//! wrapped withdrawal is paid out only by redeeming wCSPR against it, under
+//! the same challenge window and dispute checks as `finalize`. An
+//! assignment is remembered by leaf, so if the withdrawal is cancelled and
+//! the leaf proven again, the new withdrawal still pays the assignee.
+
+use alloc::vec::Vec;
+use casper_contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
+use casper_types::{
+    account::AccountHash,
+    bytesrepr::{self, FromBytes, ToBytes},
+    Key, U512,
+};
+
+use crate::{
+    envelope::{Envelope, Versioned},
+    error::Error,
+    merkle,
+    store::{self, IndexedDict, TypedDict},
+    types::WithdrawalStatus,
+};
+
+const DICT_PENDING_WITHDRAWALS: &str = "pending_withdrawals";
+const KEY_CHALLENGE_WINDOW: &str = "withdrawal_challenge_window";
+const DICT_DISPUTED_ROOTS: &str = "disputed_roots";
+const DICT_ASSIGNMENTS: &str = "withdrawal_assignments";
+pub const KEY_EXPIRY_WINDOW: &str = "withdrawal_expiry_window";
+const KEY_GRACE_WINDOW: &str = "withdrawal_grace_window";
+
+/// A proven withdrawal waiting out the challenge window
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PendingWithdrawal {
+    /// Purse (`Key::URef`) or account (`Key::Account`) to pay
+    pub recipient: Key,
+    pub amount: U512,
+    /// Withdrawal leaf the proof opened
+    pub leaf: [u8; 32],
+    /// State root the proof was checked against
+    pub root: U512,
+    /// Block height of the request
+    pub requested_at: u64,
+    pub status: WithdrawalStatus,
+    /// Block height it expired at, while `Expired`
+    pub expired_at: Option<u64>,
+}
+
+impl Versioned for PendingWithdrawal {
+    const VERSION: u8 = 1;
+
+    fn encoded_length(&self) -> usize {
+        self.recipient.serialized_length()
+            + self.amount.serialized_length()
+            + self.leaf.serialized_length()
+            + self.root.serialized_length()
+            + self.requested_at.serialized_length()
+            + self.status.serialized_length()
+            + self.expired_at.serialized_length()
+    }
+
+    fn encode(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        self.recipient.write_bytes(writer)?;
+        self.amount.write_bytes(writer)?;
+        self.leaf.write_bytes(writer)?;
+        self.root.write_bytes(writer)?;
+        self.requested_at.write_bytes(writer)?;
+        self.status.write_bytes(writer)?;
+        self.expired_at.write_bytes(writer)
+    }
+
+    fn decode(_version: u8, bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (recipient, rem) = Key::from_bytes(bytes)?;
+        let (amount, rem) = U512::from_bytes(rem)?;
+        let (leaf, rem) = <[u8; 32]>::from_bytes(rem)?;
+        let (root, rem) = U512::from_bytes(rem)?;
+        let (requested_at, rem) = u64::from_bytes(rem)?;
+        let (status, rem) = WithdrawalStatus::from_bytes(rem)?;
+        let (expired_at, rem) = Option::<u64>::from_bytes(rem)?;
+        Ok((
+            PendingWithdrawal {
+                recipient,
+                amount,
+                leaf,
+                root,
+                requested_at,
+                status,
+                expired_at,
+            },
+            rem,
+        ))
+    }
+}
+
+fn pending() -> IndexedDict<Envelope<PendingWithdrawal>> {
+    IndexedDict::open(DICT_PENDING_WITHDRAWALS)
+}
+
+fn disputed_roots() -> TypedDict<[u8; 32], bool> {
+    TypedDict::open(DICT_DISPUTED_ROOTS)
+}
+
+fn assignments() -> TypedDict<[u8; 32], Key> {
+    TypedDict::open(DICT_ASSIGNMENTS)
+}
+
+fn root_key(root: U512) -> [u8; 32] {
+    merkle::root_bytes(root).unwrap_or_revert_with(Error::InvalidArgument)
+}
+
+/// Whether `root` has been disputed
+pub fn is_disputed(root: U512) -> bool {
+    disputed_roots().contains(&root_key(root))
+}
+
+/// Flag `root` as bad so nothing proven against it is paid out
+pub fn dispute_root(root: U512) {
+    disputed_roots().set(&root_key(root), true);
+}
+
+fn get(index: u64) -> PendingWithdrawal {
+    pending()
+        .get(index)
+        .unwrap_or_revert_with(Error::UnknownWithdrawal)
+        .into_inner()
+}
+
+fn require_pending(withdrawal: &PendingWithdrawal) {
+    require_status(withdrawal, WithdrawalStatus::Pending).unwrap_or_revert();
+}
+
+fn require_status(withdrawal: &PendingWithdrawal, status: WithdrawalStatus) -> Result<(), Error> {
+    match withdrawal.status {
+        current if current == status => Ok(()),
+        WithdrawalStatus::Claimed => Err(Error::WithdrawalAlreadyClaimed),
+        _ => Err(Error::WithdrawalNotPending),
+    }
+}
+
+/// Check a withdrawal in `status` can be paid out at block `now`: its root
+/// is not `disputed` and its challenge `window` has passed
+pub fn check_payable(
+    withdrawal: &PendingWithdrawal,
+    status: WithdrawalStatus,
+    disputed: bool,
+    now: u64,
+    window: u64,
+) -> Result<(), Error> {
+    require_status(withdrawal, status)?;
+    if disputed {
+        return Err(Error::RootDisputed);
+    }
+    if now < withdrawal.requested_at.saturating_add(window) {
+        return Err(Error::ChallengeWindowOpen);
+    }
+    Ok(())
+}
+
+/// Revert unless `withdrawal`, in `status`, can be paid out now
+fn require_payable(withdrawal: &PendingWithdrawal, status: WithdrawalStatus) {
+    let window: u64 = store::read_key(KEY_CHALLENGE_WINDOW);
+    check_payable(
+        withdrawal,
+        status,
+        is_disputed(withdrawal.root),
+        runtime::get_block_height(),
+        window,
+    )
+    .unwrap_or_revert();
+}
+
+/// Create the queue; withdrawals wait `window` blocks before paying out and
+/// expired ones can be reactivated for `grace` blocks. Expiry starts off.
+pub fn init(window: u64, grace: u64) {
+    IndexedDict::<Envelope<PendingWithdrawal>>::create(DICT_PENDING_WITHDRAWALS);
+    TypedDict::<[u8; 32], bool>::create(DICT_DISPUTED_ROOTS);
+    TypedDict::<[u8; 32], Key>::create(DICT_ASSIGNMENTS);
+    store::new_key(KEY_CHALLENGE_WINDOW, window);
+    store::new_key(KEY_EXPIRY_WINDOW, 0u64);
+    store::new_key(KEY_GRACE_WINDOW, grace);
+}
+
+/// Expire withdrawals left unclaimed `window` blocks after their challenge
+/// window closes; 0 turns expiry off
+pub fn set_expiry_window(window: u64) {
+    store::write_key(KEY_EXPIRY_WINDOW, window);
+}
+
+/// Verify queue storage after install
+pub fn self_check() {
+    IndexedDict::<Envelope<PendingWithdrawal>>::require(DICT_PENDING_WITHDRAWALS);
+    store::require_key(DICT_DISPUTED_ROOTS);
+    store::require_key(DICT_ASSIGNMENTS);
+    store::require_key(KEY_CHALLENGE_WINDOW);
+    let _expiry: u64 = store::read_key(KEY_EXPIRY_WINDOW);
+    let _grace: u64 = store::read_key(KEY_GRACE_WINDOW);
+}
+
+/// Queue a proven withdrawal requested now and return its index
+///
+/// A leaf assigned before pays its assignee instead of `recipient`.
+pub fn enqueue(recipient: Key, amount: U512, leaf: [u8; 32], root: U512) -> u64 {
+    let recipient = assignments().get(&leaf).unwrap_or(recipient);
+    pending().push(Envelope(PendingWithdrawal {
+        recipient,
+        amount,
+        leaf,
+        root,
+        requested_at: runtime::get_block_height(),
+        status: WithdrawalStatus::Pending,
+        expired_at: None,
+    }))
+}
+
+/// Mark a pending withdrawal claimed once its window has passed and return
+/// it
+///
+/// Reverts if its root has been disputed.
+pub fn finalize(index: u64) -> PendingWithdrawal {
+    let mut withdrawal = get(index);
+    require_payable(&withdrawal, WithdrawalStatus::Pending);
+    withdrawal.status = WithdrawalStatus::Claimed;
+    pending().set(index, Envelope(withdrawal.clone()));
+    withdrawal
+}
+
+/// A wrapped withdrawal that wCSPR can be redeemed against now
+///
+/// Reverts while its window is open and for good once its root is disputed.
+pub fn redeemable(index: u64) -> PendingWithdrawal {
+    let withdrawal = get(index);
+    require_payable(&withdrawal, WithdrawalStatus::Wrapped);
+    withdrawal
+}
+
+/// Mark a wrapped withdrawal claimed once all of it has been redeemed
+pub fn settle_wrapped(index: u64) {
+    let mut withdrawal = redeemable(index);
+    withdrawal.status = WithdrawalStatus::Claimed;
+    pending().set(index, Envelope(withdrawal));
+}
+
+/// Current status of a withdrawal
+pub fn status(index: u64) -> WithdrawalStatus {
+    get(index).status
+}
+
+/// Pay a pending withdrawal to `signer`'s account to `new_recipient`
+/// instead and return it
+///
+/// The caller has checked `signer`'s signature over the assignment.
+pub fn assign(index: u64, signer: AccountHash, new_recipient: Key) -> PendingWithdrawal {
+    let mut withdrawal = get(index);
+    require_pending(&withdrawal);
+    if withdrawal.recipient != Key::Account(signer) {
+        runtime::revert(Error::NotWithdrawalRecipient);
+    }
+    if is_disputed(withdrawal.root) {
+        runtime::revert(Error::RootDisputed);
+    }
+    withdrawal.recipient = new_recipient;
+    assignments().set(&withdrawal.leaf, new_recipient);
+    pending().set(index, Envelope(withdrawal.clone()));
+    withdrawal
+}
+
+/// Mark a pending withdrawal to `owner` wrapped and return it
+pub fn wrap(index: u64, owner: Key) -> PendingWithdrawal {
+    let mut withdrawal = get(index);
+    require_pending(&withdrawal);
+    if withdrawal.recipient != owner {
+        runtime::revert(Error::NotWithdrawalRecipient);
+    }
+    if is_disputed(withdrawal.root) {
+        runtime::revert(Error::RootDisputed);
+    }
+    withdrawal.status = WithdrawalStatus::Wrapped;
+    pending().set(index, Envelope(withdrawal.clone()));
+    withdrawal
+}
+
+/// Drop a pending withdrawal proven against a disputed root and return it
+pub fn cancel(index: u64) -> PendingWithdrawal {
+    let mut withdrawal = get(index);
+    require_pending(&withdrawal);
+    if !is_disputed(withdrawal.root) {
+        runtime::revert(Error::RootNotDisputed);
+    }
+    withdrawal.status = WithdrawalStatus::Cancelled;
+    pending().set(index, Envelope(withdrawal.clone()));
+    withdrawal
+}
+
+/// Expire a withdrawal left unclaimed past the expiry window and return it
+///
+/// The caller moves its funds to the insurance fund.
+pub fn expire(index: u64) -> PendingWithdrawal {
+    let mut withdrawal = get(index);
+    require_pending(&withdrawal);
+    let expiry: u64 = store::read_key(KEY_EXPIRY_WINDOW);
+    if expiry == 0 {
+        runtime::revert(Error::WithdrawalExpiryDisabled);
+    }
+    let window: u64 = store::read_key(KEY_CHALLENGE_WINDOW);
+    let now = runtime::get_block_height();
+    let expires_at = withdrawal
+        .requested_at
+        .saturating_add(window)
+        .saturating_add(expiry);
+    if now < expires_at {
+        runtime::revert(Error::ExpiryWindowOpen);
+    }
+    withdrawal.status = WithdrawalStatus::Expired;
+    withdrawal.expired_at = Some(now);
+    pending().set(index, Envelope(withdrawal.clone()));
+    withdrawal
+}
+
+/// Return an expired withdrawal to the queue within its grace window and
+/// return it
+///
+/// Its challenge window has already passed, so it can be finalized at once.
+/// The caller moves its funds back from the insurance fund.
+pub fn reactivate(index: u64) -> PendingWithdrawal {
+    let mut withdrawal = get(index);
+    let expired_at = match (withdrawal.status, withdrawal.expired_at) {
+        (WithdrawalStatus::Expired, Some(expired_at)) => expired_at,
+        _ => runtime::revert(Error::WithdrawalNotExpired),
+    };
+    let grace: u64 = store::read_key(KEY_GRACE_WINDOW);
+    if runtime::get_block_height() >= expired_at.saturating_add(grace) {
+        runtime::revert(Error::GraceWindowClosed);
+    }
+    withdrawal.status = WithdrawalStatus::Pending;
+    withdrawal.expired_at = None;
+    pending().set(index, Envelope(withdrawal.clone()));
+    withdrawal
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WINDOW: u64 = 10;
+
+    fn wrapped() -> PendingWithdrawal {
+        PendingWithdrawal {
+            recipient: Key::Account(AccountHash::new([1; 32])),
+            amount: U512::from(1_000u64),
+            leaf: [2; 32],
+            root: U512::from(3u64),
+            requested_at: 100,
+            status: WithdrawalStatus::Wrapped,
+            expired_at: None,
+        }
+    }
+
+    #[test]
+    fn wrapped_withdrawals_are_redeemable_only_after_the_window() {
+        let withdrawal = wrapped();
+        let payable =
+            |now| check_payable(&withdrawal, WithdrawalStatus::Wrapped, false, now, WINDOW);
+        assert_eq!(payable(109), Err(Error::ChallengeWindowOpen));
+        assert_eq!(payable(110), Ok(()));
+        // Nor can it be finalized as a plain pending withdrawal
+        assert_eq!(
+            check_payable(&withdrawal, WithdrawalStatus::Pending, false, 110, WINDOW),
+            Err(Error::WithdrawalNotPending)
+        );
+    }
+
+    #[test]
+    fn disputing_the_root_after_a_wrap_blocks_redemption() {
+        let withdrawal = wrapped();
+        for now in [105, 110, 1_000] {
+            assert_eq!(
+                check_payable(&withdrawal, WithdrawalStatus::Wrapped, true, now, WINDOW),
+                Err(Error::RootDisputed)
+            );
+        }
+    }
+
+    #[test]
+    fn claimed_withdrawals_are_not_paid_twice() {
+        let claimed = PendingWithdrawal {
+            status: WithdrawalStatus::Claimed,
+            ..wrapped()
+        };
+        for status in [WithdrawalStatus::Pending, WithdrawalStatus::Wrapped] {
+            assert_eq!(
+                check_payable(&claimed, status, false, 110, WINDOW),
+                Err(Error::WithdrawalAlreadyClaimed)
+            );
+        }
+    }
+}
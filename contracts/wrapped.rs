@@ -0,0 +1,98 @@
+//! Bridge side of the wCSPR companion token
+//!
+//! The recipient of a pending withdrawal can wrap it: the bridge mints the
+//! same amount of wCSPR (see `wcspr.rs`) to that recipient at once. wCSPR is
+//! redeemed for CSPR against a named wrapped withdrawal, and only once that
+//! withdrawal could have been finalized: its challenge window has passed
+//! and its root is undisputed. Each redemption draws down what is left of
+//! the withdrawal, which is marked claimed when nothing is.
+//!
+//! So the bridge never pays out for wCSPR before the withdrawal behind the
+//! payment is final. If a root is disputed after a wrap, nothing can be
+//! redeemed against that withdrawal; the wCSPR minted for it stays in
+//! circulation but the bridge pays nothing more than the wrapped
+//! withdrawals that did finalize.
+
+use casper_contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
+use casper_types::{contracts::ContractPackageHash, runtime_args, Key, U256, U512};
+
+use crate::{
+    error::Error,
+    store::{self, TypedDict},
+    wcspr_entry_points::{ARG_AMOUNT, ARG_OWNER, EP_BURN, EP_MINT},
+};
+
+pub const KEY_WCSPR_TOKEN: &str = "wcspr_token";
+const DICT_WRAPPED_UNREDEEMED: &str = "wrapped_unredeemed";
+
+fn unredeemed() -> TypedDict<u64, U512> {
+    TypedDict::open(DICT_WRAPPED_UNREDEEMED)
+}
+
+/// No token is configured until the admin sets one
+pub fn init() {
+    store::new_key(KEY_WCSPR_TOKEN, Option::<ContractPackageHash>::None);
+    TypedDict::<u64, U512>::create(DICT_WRAPPED_UNREDEEMED);
+}
+
+/// Verify wCSPR storage after install
+pub fn self_check() {
+    let _token: Option<ContractPackageHash> = store::read_key(KEY_WCSPR_TOKEN);
+    store::require_key(DICT_WRAPPED_UNREDEEMED);
+}
+
+pub fn set_token(token: ContractPackageHash) {
+    store::write_key(KEY_WCSPR_TOKEN, Some(token));
+}
+
+fn token() -> ContractPackageHash {
+    let token: Option<ContractPackageHash> = store::read_key(KEY_WCSPR_TOKEN);
+    token.unwrap_or_revert_with(Error::WcsprTokenNotSet)
+}
+
+/// CEP-18 amount for `amount` motes
+fn token_amount(amount: U512) -> U256 {
+    let mut bytes = [0u8; 64];
+    amount.to_little_endian(&mut bytes);
+    if bytes[32..].iter().any(|b| *b != 0) {
+        runtime::revert(Error::InvalidAmount);
+    }
+    U256::from_little_endian(&bytes[..32])
+}
+
+/// Mint `amount` wCSPR to `owner` against wrapped withdrawal `index`
+pub fn mint(index: u64, owner: Key, amount: U512) {
+    unredeemed().set(&index, amount);
+    runtime::call_versioned_contract::<()>(
+        token(),
+        None,
+        EP_MINT,
+        runtime_args! { ARG_OWNER => owner, ARG_AMOUNT => token_amount(amount) },
+    );
+}
+
+/// Take `amount` off what can still be redeemed against withdrawal `index`
+/// and return what is left
+pub fn draw(index: u64, amount: U512) -> U512 {
+    let left = unredeemed()
+        .get(&index)
+        .unwrap_or_default()
+        .checked_sub(amount)
+        .unwrap_or_revert_with(Error::RedemptionExceedsWrapped);
+    if left.is_zero() {
+        unredeemed().remove(&index);
+    } else {
+        unredeemed().set(&index, left);
+    }
+    left
+}
+
+/// Burn `amount` of `owner`'s wCSPR, reverting if the balance is too low
+pub fn burn(owner: Key, amount: U512) {
+    runtime::call_versioned_contract::<()>(
+        token(),
+        None,
+        EP_BURN,
+        runtime_args! { ARG_OWNER => owner, ARG_AMOUNT => token_amount(amount) },
+    );
+}
@@ -1,6 +1,13 @@
 #!/bin/bash
+# usage: scripts/compile.sh <testnet|mainnet|integration>
 set -e
 
+NETWORK=$1
+case "$NETWORK" in
+  testnet|mainnet|integration) ;;
+  *) echo "usage: $0 <testnet|mainnet|integration>" >&2; exit 1 ;;
+esac
+
 echo "Starting build pipeline for Casper Accelerate..."
 
 # Compile ZK Circuits
@@ -19,8 +26,10 @@ if command -v cargo >/dev/null 2>&1; then
   # Use nightly for better no_std support and optimizations
   # Use -Z build-std to recompile core and alloc from source with MVP WASM (no bulk memory)
   # Set RUSTFLAGS explicitly to ensure they apply to build-std compilation
-  RUSTFLAGS="-C target-feature=-bulk-memory,-sign-ext,-mutable-globals" \
-    $HOME/.cargo/bin/cargo +nightly build -Z build-std=core,alloc --release --target wasm32-unknown-unknown
+  # RUSTFLAGS replaces .cargo/config.toml, so it repeats --allow-undefined from there
+  RUSTFLAGS="-C target-feature=-bulk-memory,-sign-ext,-mutable-globals -C link-arg=--allow-undefined" \
+    $HOME/.cargo/bin/cargo +nightly build -Z build-std=core,alloc --release --target wasm32-unknown-unknown \
+    --features "$NETWORK"
   
   # Optimize and lower bulk memory operations to MVP WASM for Casper compatibility
   if command -v wasm-opt >/dev/null 2>&1; then